@@ -0,0 +1,377 @@
+use super::definitions::{get_field_by_code, get_type_name, DEFINITIONS};
+use super::error::{Error, Result};
+use super::types::{
+    PATHSET_END, PATH_SEPARATOR, PATH_STEP_ACCOUNT, PATH_STEP_CURRENCY, PATH_STEP_ISSUER,
+};
+use super::utils::XRPL_ALPHABET;
+use serde_json::{Map, Value};
+
+/// Parses a binary serialized object, such as a `tx_blob` or a ledger entry, into the JSON
+/// representation used by rippled. UInt64 fields are returned as 16 character hex strings and
+/// issued currency values as plain decimal strings, as rippled returns them.
+pub fn from_bytes(bytes: &[u8]) -> Result<Value> {
+    let mut deserializer = Deserializer::new(bytes);
+    let object = deserializer.read_object(false)?;
+    Ok(Value::Object(object))
+}
+
+pub struct Deserializer<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Deserializer<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Self { input }
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.input.len() < len {
+            return Err(Error::UnexpectedEndOfInput);
+        }
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read(1)?[0])
+    }
+
+    fn read_u64(&mut self) -> Result<u64> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.read(8)?);
+        Ok(u64::from_be_bytes(bytes))
+    }
+
+    fn read_hex(&mut self, len: usize) -> Result<String> {
+        Ok(hex::encode_upper(self.read(len)?))
+    }
+
+    fn read_variable_length(&mut self) -> Result<usize> {
+        let b1 = self.read_u8()? as usize;
+        if b1 <= 192 {
+            return Ok(b1);
+        }
+        let b2 = self.read_u8()? as usize;
+        if b1 <= 240 {
+            return Ok(193 + (b1 - 193) * 256 + b2);
+        }
+        let b3 = self.read_u8()? as usize;
+        if b1 <= 254 {
+            return Ok(12481 + (b1 - 241) * 65536 + b2 * 256 + b3);
+        }
+        Err(Error::Message(format!(
+            "invalid variable length prefix: {}",
+            b1
+        )))
+    }
+
+    fn read_field_header(&mut self) -> Result<(u8, u8)> {
+        let first = self.read_u8()?;
+        let mut type_code = first >> 4;
+        let mut field_code = first & 0x0F;
+        if type_code == 0 {
+            type_code = self.read_u8()?;
+        }
+        if field_code == 0 {
+            field_code = self.read_u8()?;
+        }
+        Ok((type_code, field_code))
+    }
+
+    /// Reads fields until the input is exhausted or, for nested objects, until the object end
+    /// marker.
+    fn read_object(&mut self, nested: bool) -> Result<Map<String, Value>> {
+        let mut object = Map::new();
+        while !self.input.is_empty() {
+            let header = self.read_field_header()?;
+            let field = get_field_by_code(header.0, header.1)?;
+            if nested && field.0 == "ObjectEndMarker" {
+                return Ok(object);
+            }
+            let value = self.read_field_value(&field.0, &field.1.r#type, field.1.is_vl_encoded)?;
+            object.insert(field.0.to_owned(), value);
+        }
+        if nested {
+            return Err(Error::UnexpectedEndOfInput);
+        }
+        Ok(object)
+    }
+
+    fn read_array(&mut self) -> Result<Vec<Value>> {
+        let mut array = Vec::new();
+        loop {
+            let header = self.read_field_header()?;
+            let field = get_field_by_code(header.0, header.1)?;
+            if field.0 == "ArrayEndMarker" {
+                return Ok(array);
+            }
+            let mut element = Map::new();
+            element.insert(field.0.to_owned(), Value::Object(self.read_object(true)?));
+            array.push(Value::Object(element));
+        }
+    }
+
+    fn read_field_value(
+        &mut self,
+        name: &str,
+        field_type: &str,
+        is_vl_encoded: bool,
+    ) -> Result<Value> {
+        if is_vl_encoded {
+            let len = self.read_variable_length()?;
+            return match field_type {
+                "AccountID" => Ok(Value::String(encode_account_id(self.read(len)?))),
+                "Vector256" => Ok(Value::Array(
+                    self.read(len)?
+                        .chunks(32)
+                        .map(|hash| Value::String(hex::encode_upper(hash)))
+                        .collect(),
+                )),
+                _ => Ok(Value::String(self.read_hex(len)?)),
+            };
+        }
+        match field_type {
+            "UInt8" => {
+                let v = self.read_u8()?;
+                match name {
+                    "TransactionResult" => {
+                        Ok(type_name(&DEFINITIONS.transaction_results, v as i16))
+                    }
+                    _ => Ok(Value::from(v)),
+                }
+            }
+            "UInt16" => {
+                let v = u16::from_be_bytes([self.read_u8()?, self.read_u8()?]);
+                match name {
+                    "TransactionType" => Ok(type_name(&DEFINITIONS.transaction_types, v as i16)),
+                    "LedgerEntryType" => Ok(type_name(&DEFINITIONS.ledger_entry_types, v as i16)),
+                    _ => Ok(Value::from(v)),
+                }
+            }
+            "UInt32" => {
+                let mut bytes = [0u8; 4];
+                bytes.copy_from_slice(self.read(4)?);
+                Ok(Value::from(u32::from_be_bytes(bytes)))
+            }
+            "UInt64" => Ok(Value::String(self.read_hex(8)?)),
+            "Hash128" => Ok(Value::String(self.read_hex(16)?)),
+            "Hash160" => Ok(Value::String(self.read_hex(20)?)),
+            "Hash256" => Ok(Value::String(self.read_hex(32)?)),
+            "Amount" => self.read_amount(),
+            "STObject" => Ok(Value::Object(self.read_object(true)?)),
+            "STArray" => Ok(Value::Array(self.read_array()?)),
+            "PathSet" => self.read_path_set(),
+            _ => Err(Error::UnknownFieldType(field_type.to_owned())),
+        }
+    }
+
+    fn read_amount(&mut self) -> Result<Value> {
+        let flags = *self.input.first().ok_or(Error::UnexpectedEndOfInput)?;
+        if flags & 0x80 == 0 && flags & 0x20 != 0 {
+            // MPT amounts have a flag byte, a 64-bit value and the 24 byte MPTokenIssuanceID.
+            let sign = if flags & 0x40 != 0 { "" } else { "-" };
+            self.read_u8()?;
+            let value = format!("{}{}", sign, self.read_u64()?);
+            let mut amount = Map::new();
+            amount.insert("value".to_owned(), Value::String(value));
+            amount.insert(
+                "mpt_issuance_id".to_owned(),
+                Value::String(self.read_hex(24)?),
+            );
+            return Ok(Value::Object(amount));
+        }
+        let raw = self.read_u64()?;
+        let sign = if raw & 0x4000000000000000 != 0 {
+            ""
+        } else {
+            "-"
+        };
+        if raw & 0x8000000000000000 == 0 {
+            return Ok(Value::String(format!(
+                "{}{}",
+                sign,
+                raw & 0x3FFFFFFFFFFFFFFF
+            )));
+        }
+        let mantissa = raw & 0x003FFFFFFFFFFFFF;
+        let value = if mantissa == 0 {
+            "0".to_owned()
+        } else {
+            let exponent = ((raw >> 54) & 0xFF) as i32 - 97;
+            format!("{}{}", sign, format_issued_value(mantissa, exponent))
+        };
+        let mut amount = Map::new();
+        amount.insert("value".to_owned(), Value::String(value));
+        amount.insert(
+            "currency".to_owned(),
+            Value::String(decode_currency_code(self.read(20)?)),
+        );
+        amount.insert(
+            "issuer".to_owned(),
+            Value::String(encode_account_id(self.read(20)?)),
+        );
+        Ok(Value::Object(amount))
+    }
+
+    fn read_path_set(&mut self) -> Result<Value> {
+        let mut paths = Vec::new();
+        let mut path = Vec::new();
+        loop {
+            let step_type = self.read_u8()?;
+            if step_type == PATHSET_END || step_type == PATH_SEPARATOR {
+                paths.push(Value::Array(std::mem::take(&mut path)));
+                if step_type == PATHSET_END {
+                    return Ok(Value::Array(paths));
+                }
+                continue;
+            }
+            let mut step = Map::new();
+            if step_type & PATH_STEP_ACCOUNT != 0 {
+                step.insert(
+                    "account".to_owned(),
+                    Value::String(encode_account_id(self.read(20)?)),
+                );
+            }
+            if step_type & PATH_STEP_CURRENCY != 0 {
+                step.insert(
+                    "currency".to_owned(),
+                    Value::String(decode_currency_code(self.read(20)?)),
+                );
+            }
+            if step_type & PATH_STEP_ISSUER != 0 {
+                step.insert(
+                    "issuer".to_owned(),
+                    Value::String(encode_account_id(self.read(20)?)),
+                );
+            }
+            path.push(Value::Object(step));
+        }
+    }
+}
+
+fn type_name(codes: &'static std::collections::HashMap<String, i16>, code: i16) -> Value {
+    match get_type_name(codes, code) {
+        Some(name) => Value::String(name.to_owned()),
+        None => Value::from(code),
+    }
+}
+
+fn encode_account_id(account_id: &[u8]) -> String {
+    bs58::encode([&[0x00], account_id].concat())
+        .with_alphabet(&XRPL_ALPHABET)
+        .with_check()
+        .into_string()
+}
+
+/// Decodes a 160-bit currency code, returning standard codes as their 3 character form.
+fn decode_currency_code(currency: &[u8]) -> String {
+    if currency.iter().all(|b| *b == 0) {
+        return "XRP".to_owned();
+    }
+    let code = &currency[12..15];
+    let is_standard = currency[..12].iter().all(|b| *b == 0)
+        && currency[15..].iter().all(|b| *b == 0)
+        && code.iter().all(|b| b.is_ascii_alphanumeric());
+    if is_standard {
+        return String::from_utf8_lossy(code).into_owned();
+    }
+    hex::encode_upper(currency)
+}
+
+/// Formats `mantissa * 10^exponent` as a plain decimal string without trailing zeros.
+fn format_issued_value(mantissa: u64, exponent: i32) -> String {
+    let mantissa = mantissa.to_string();
+    let digits = mantissa.trim_end_matches('0');
+    let exponent = exponent + (mantissa.len() - digits.len()) as i32;
+    if exponent >= 0 {
+        return format!("{}{}", digits, "0".repeat(exponent as usize));
+    }
+    let point = digits.len() as i32 + exponent;
+    if point > 0 {
+        let (int, frac) = digits.split_at(point as usize);
+        format!("{}.{}", int, frac)
+    } else {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_bytes;
+    use crate::ser::to_bytes;
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CodecFixtures {
+        account_state: Vec<Fixture>,
+        transactions: Vec<Fixture>,
+    }
+
+    #[derive(Deserialize)]
+    struct Fixture {
+        binary: String,
+        json: Value,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let transaction = json!({
+          "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+          "Expiration": 595640108,
+          "Fee": "10",
+          "Flags": 524288,
+          "OfferSequence": 1752791,
+          "Sequence": 1752792,
+          "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3",
+          "TakerGets": "15000000000",
+          "TakerPays": {
+            "currency": "USD",
+            "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+            "value": "7072.8"
+          },
+          "TransactionType": "OfferCreate",
+          "TxnSignature": "30440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C"
+        });
+        let bytes = to_bytes(&transaction).unwrap();
+        assert_eq!(from_bytes(&bytes).unwrap(), transaction);
+    }
+
+    #[test]
+    fn test_codec_fixtures() {
+        let codec_fixtures_bytes = include_bytes!("../test/fixtures/codec-fixtures.json");
+        let codec_fixtures: CodecFixtures = serde_json::from_slice(codec_fixtures_bytes).unwrap();
+        for fixture in codec_fixtures
+            .account_state
+            .iter()
+            .chain(codec_fixtures.transactions.iter())
+        {
+            let bytes = hex::decode(&fixture.binary).unwrap();
+            assert_eq!(from_bytes(&bytes).unwrap(), fixture.json);
+        }
+    }
+
+    #[test]
+    fn test_mpt_amount() {
+        let bytes =
+            hex::decode("61600000000000000064000000016203F49C21D5D6E022CB16DE3538F248662FC73C")
+                .unwrap();
+        assert_eq!(
+            from_bytes(&bytes).unwrap(),
+            json!({
+                "Amount": {
+                    "value": "100",
+                    "mpt_issuance_id": "000000016203F49C21D5D6E022CB16DE3538F248662FC73C"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_truncated_input() {
+        let bytes = hex::decode("1200002200000000240000003E61400000").unwrap();
+        assert!(from_bytes(&bytes).is_err());
+    }
+}
@@ -9,6 +9,20 @@ const DEFINITIONS_JSON: &str = include_str!("definitions.json");
 
 lazy_static! {
     pub static ref DEFINITIONS: Definitions = serde_json::from_str(&DEFINITIONS_JSON).unwrap();
+    /// The bundled fields by name. The first of any fields sharing a name is kept, as the first
+    /// is the one a scan of the fields finds.
+    static ref FIELDS_BY_NAME: HashMap<&'static str, &'static Field> = {
+        let mut fields = HashMap::new();
+        for field in &DEFINITIONS.fields {
+            fields.entry(field.0.as_str()).or_insert(field);
+        }
+        fields
+    };
+    /// The field and type codes of the bundled fields, for those whose type is known.
+    static ref FIELD_CODES: HashMap<&'static str, (u8, u8)> = FIELDS_BY_NAME
+        .keys()
+        .filter_map(|name| Some((*name, DEFINITIONS.resolve_field_codes(name).ok()?)))
+        .collect();
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -18,6 +32,10 @@ pub struct Definitions {
     pub fields: Vec<Field>,
     pub transaction_types: HashMap<String, i16>,
     pub ledger_entry_types: HashMap<String, i16>,
+    pub transaction_results: HashMap<String, i16>,
+    /// The hash of these definitions, as returned by rippled's `server_definitions` method.
+    #[serde(rename = "hash", default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -44,58 +62,145 @@ pub struct FieldInfo {
     pub r#type: String,
 }
 
-/// Returns a boolean indicating whether the field is a signing field, i.e. should be included
-/// in the binary representation for when signing.
-pub fn is_signing_field(field_name: &str) -> Option<bool> {
-    for field in &DEFINITIONS.fields {
-        if field.0 == field_name {
-            return Some(field.1.is_signing_field);
-        }
+impl Definitions {
+    /// Parses definitions in the format of rippled's `definitions.json`, e.g. to serialize fields
+    /// added by amendments that are newer than the bundled definitions.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| Error::Message(e.to_string()))
     }
-    None
-}
 
-pub fn is_serialized_field(field_name: &str) -> Option<bool> {
-    for field in &DEFINITIONS.fields {
-        if field.0 == field_name {
-            return Some(field.1.is_serialized);
+    // Whether these are the bundled definitions, whose fields are indexed by name. Definitions
+    // loaded at runtime have public fields that may change, so they are scanned instead.
+    fn is_bundled(&self) -> bool {
+        std::ptr::eq(self, &*DEFINITIONS)
+    }
+
+    fn get_field(&self, field_name: &str) -> Option<&Field> {
+        if self.is_bundled() {
+            return FIELDS_BY_NAME.get(field_name).copied();
         }
+        self.fields.iter().find(|field| field.0 == field_name)
     }
-    None
-}
 
-pub fn get_field_code_and_type_code(field_name: &str) -> Result<(u8, u8)> {
-    for field in &DEFINITIONS.fields {
-        if field.0 != field_name {
-            continue;
+    /// Returns a boolean indicating whether the field is a signing field, i.e. should be included
+    /// in the binary representation for when signing.
+    pub fn is_signing_field(&self, field_name: &str) -> Option<bool> {
+        self.get_field(field_name)
+            .map(|field| field.1.is_signing_field)
+    }
+
+    pub fn is_serialized_field(&self, field_name: &str) -> Option<bool> {
+        self.get_field(field_name)
+            .map(|field| field.1.is_serialized)
+    }
+
+    pub fn get_field_code_and_type_code(&self, field_name: &str) -> Result<(u8, u8)> {
+        if self.is_bundled() {
+            if let Some(codes) = FIELD_CODES.get(field_name) {
+                return Ok(*codes);
+            }
         }
-        let field_type = DEFINITIONS
+        self.resolve_field_codes(field_name)
+    }
+
+    fn resolve_field_codes(&self, field_name: &str) -> Result<(u8, u8)> {
+        let field = self
+            .get_field(field_name)
+            .ok_or(Error::UnknownFieldName(field_name.to_owned()))?;
+        let field_type = self
             .types
             .get(&field.1.r#type)
             .ok_or(Error::UnknownFieldType(field.1.r#type.to_owned()))?;
-        return Ok((
+        Ok((
             field
                 .1
                 .nth
                 .try_into()
                 .map_err(|e| Error::Message(format!("{:?}", e)))?,
-            field_type
-                .clone()
+            (*field_type)
                 .try_into()
                 .map_err(|e| Error::Message(format!("{:?}", e)))?,
-        ));
+        ))
+    }
+
+    pub fn get_transaction_type(&self, transaction_type_name: &str) -> Result<i16> {
+        if let Some(transaction_type) = self.transaction_types.get(transaction_type_name) {
+            return Ok(*transaction_type);
+        }
+        if let Some(transaction_type) = self.ledger_entry_types.get(transaction_type_name) {
+            return Ok(*transaction_type);
+        }
+        Err(Error::InvalidTransactionType(
+            transaction_type_name.to_owned(),
+        ))
     }
-    Err(Error::UnknownFieldName(field_name.to_owned()))
+}
+
+/// Returns a boolean indicating whether the field is a signing field, i.e. should be included
+/// in the binary representation for when signing.
+pub fn is_signing_field(field_name: &str) -> Option<bool> {
+    DEFINITIONS.is_signing_field(field_name)
+}
+
+pub fn is_serialized_field(field_name: &str) -> Option<bool> {
+    DEFINITIONS.is_serialized_field(field_name)
+}
+
+pub fn get_field_code_and_type_code(field_name: &str) -> Result<(u8, u8)> {
+    DEFINITIONS.get_field_code_and_type_code(field_name)
 }
 
 pub fn get_transaction_type(transaction_type_name: &str) -> Result<i16> {
-    if let Some(transaction_type) = DEFINITIONS.transaction_types.get(transaction_type_name) {
-        return Ok(*transaction_type);
-    }
-    if let Some(transaction_type) = DEFINITIONS.ledger_entry_types.get(transaction_type_name) {
-        return Ok(*transaction_type);
+    DEFINITIONS.get_transaction_type(transaction_type_name)
+}
+
+/// Returns the field with the provided type code and field code, as read from a field header.
+pub fn get_field_by_code(type_code: u8, field_code: u8) -> Result<&'static Field> {
+    DEFINITIONS
+        .fields
+        .iter()
+        .find(|field| {
+            field.1.nth == field_code as i16
+                && DEFINITIONS.types.get(&field.1.r#type) == Some(&(type_code as i16))
+        })
+        .ok_or(Error::UnknownFieldCode(type_code, field_code))
+}
+
+/// Returns the name of a transaction type, ledger entry type or transaction result code, looked
+/// up in the definitions map for the field that holds it.
+pub fn get_type_name(codes: &'static HashMap<String, i16>, code: i16) -> Option<&'static str> {
+    codes
+        .iter()
+        .find(|(_, c)| **c == code)
+        .map(|(name, _)| name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DEFINITIONS;
+
+    #[test]
+    fn indexed_lookups_match_scan() {
+        // A copy is not the bundled definitions, so its lookups scan the fields.
+        let scanned = DEFINITIONS.clone();
+        let names = DEFINITIONS
+            .fields
+            .iter()
+            .map(|field| field.0.as_str())
+            .chain(["NotAField"]);
+        for name in names {
+            assert_eq!(
+                DEFINITIONS.is_signing_field(name),
+                scanned.is_signing_field(name)
+            );
+            assert_eq!(
+                DEFINITIONS.is_serialized_field(name),
+                scanned.is_serialized_field(name)
+            );
+            assert_eq!(
+                format!("{:?}", DEFINITIONS.get_field_code_and_type_code(name)),
+                format!("{:?}", scanned.get_field_code_and_type_code(name))
+            );
+        }
     }
-    Err(Error::InvalidTransactionType(
-        transaction_type_name.to_owned(),
-    ))
 }
@@ -27,8 +27,13 @@ pub enum Error {
 
     InvalidAmount(ParseIntError, String),
     InvalidIssuedCurrencyAmount(String),
+    InvalidCurrencyCode(String),
+    InvalidHash(String),
 
     InvalidTransactionType(String),
+
+    UnknownFieldCode(u8, u8),
+    UnexpectedEndOfInput,
 }
 
 impl ser::Error for Error {
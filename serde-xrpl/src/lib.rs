@@ -1,6 +1,7 @@
-mod definitions;
+pub mod definitions;
 pub mod error;
 pub mod types;
 pub mod utils;
 pub mod ser;
-mod hash_prefixes;
+pub mod de;
+pub mod hash_prefixes;
@@ -1,12 +1,11 @@
-use crate::definitions::is_signing_field;
 use crate::hash_prefixes;
 
-use super::definitions::{get_field_code_and_type_code, get_transaction_type, is_serialized_field};
+use super::definitions::{Definitions, DEFINITIONS};
 use super::error::{Error, Result};
-use super::types::{Amount, Blob, Hash256, Value, Vector256};
+use super::types::{Amount, Blob, Hash128, Hash160, Hash256, STArray, STObject, Value, Vector256};
 use super::utils::{
     decode_base58, encode_field_id, encode_issued_currency_amount, encode_variable_length,
-    StringSerializer,
+    RoundingMode, StringSerializer,
 };
 use serde::{ser, Serialize};
 
@@ -15,6 +14,9 @@ pub struct SerializerOptions {
     pub prefix: Option<Vec<u8>>,
     pub suffix: Option<Vec<u8>>,
     pub signing_fields_only: bool,
+    /// The rounding applied to issued currency amounts with more significant digits than the
+    /// XRPL can represent.
+    pub rounding_mode: RoundingMode,
 }
 
 #[derive(PartialEq, PartialOrd, Clone, Debug)]
@@ -40,8 +42,8 @@ impl FieldHeader {
     }
 }
 
-#[derive(Default)]
-pub struct Serializer {
+pub struct Serializer<'d> {
+    definitions: &'d Definitions,
     options: SerializerOptions,
     sequence: usize,
     field: Option<(FieldHeader, Value)>,
@@ -49,12 +51,43 @@ pub struct Serializer {
     output: Vec<u8>,
 }
 
+impl<'d> Serializer<'d> {
+    pub fn new(definitions: &'d Definitions, options: SerializerOptions) -> Self {
+        Serializer {
+            definitions,
+            options,
+            sequence: 0,
+            field: None,
+            fields: Vec::new(),
+            output: Vec::new(),
+        }
+    }
+}
+
+impl Default for Serializer<'static> {
+    fn default() -> Self {
+        Serializer::new(&DEFINITIONS, SerializerOptions::default())
+    }
+}
+
 pub fn to_bytes_with_opts<T>(value: &T, opts: Option<SerializerOptions>) -> Result<Vec<u8>>
 where
     T: Serialize,
 {
-    let mut serializer = Serializer::default();
-    serializer.options = opts.unwrap_or_default();
+    to_bytes_with_definitions(value, opts, &DEFINITIONS)
+}
+
+/// Serializes `value` using the given field and type definitions instead of the bundled ones,
+/// e.g. definitions loaded at runtime from a network with amendments this crate does not know.
+pub fn to_bytes_with_definitions<T>(
+    value: &T,
+    opts: Option<SerializerOptions>,
+    definitions: &Definitions,
+) -> Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    let mut serializer = Serializer::new(definitions, opts.unwrap_or_default());
     if let Some(prefix) = &serializer.options.prefix {
         serializer.output.append(&mut prefix.clone());
     }
@@ -62,10 +95,15 @@ where
     serializer
         .fields
         .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let rounding_mode = serializer.options.rounding_mode;
     for (header, value) in &mut serializer.fields {
-        serializer
-            .output
-            .append(&mut [header.to_bytes(), value.to_bytes()?.clone()].concat());
+        serializer.output.append(
+            &mut [
+                header.to_bytes(),
+                value.to_bytes_with_rounding(rounding_mode)?.clone(),
+            ]
+            .concat(),
+        );
     }
     if let Some(suffix) = &serializer.options.suffix {
         serializer.output.append(&mut suffix.clone());
@@ -90,6 +128,24 @@ where
             prefix: Some(hash_prefixes::TRANSACTION_SIG.to_vec()),
             signing_fields_only: true,
             suffix: None,
+            rounding_mode: RoundingMode::default(),
+        }),
+    )
+}
+
+/// Serializes a transaction for signing by one of the signers of a multisigning account,
+/// identified by the suffixed account ID.
+pub fn to_bytes_for_multi_signing<T>(value: &T, account_id: &[u8]) -> Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    to_bytes_with_opts(
+        value,
+        Some(SerializerOptions {
+            prefix: Some(hash_prefixes::TRANSACTION_MULTI_SIG.to_vec()),
+            signing_fields_only: true,
+            suffix: Some(account_id.to_vec()),
+            rounding_mode: RoundingMode::default(),
         }),
     )
 }
@@ -104,11 +160,64 @@ where
             prefix: Some(hash_prefixes::PAYMENT_CHANNEL_CLAIM.to_vec()),
             signing_fields_only: true,
             suffix: None,
+            rounding_mode: RoundingMode::default(),
         }),
     )
 }
 
-impl<'a> ser::Serializer for &'a mut Serializer {
+/// Returns the field id of one of the sentinel fields terminating inner objects and arrays,
+/// `ObjectEndMarker` or `ArrayEndMarker`.
+fn end_marker(name: &str, definitions: &Definitions) -> Result<Vec<u8>> {
+    let (field_code, type_code) = definitions.get_field_code_and_type_code(name)?;
+    Ok(encode_field_id(type_code, field_code))
+}
+
+/// Serializes the fields of an inner object in canonical order, followed by the object end marker.
+fn serialize_object(
+    value: &serde_json::Value,
+    options: &SerializerOptions,
+    definitions: &Definitions,
+) -> Result<STObject> {
+    let mut bytes = to_bytes_with_definitions(
+        value,
+        Some(SerializerOptions {
+            prefix: None,
+            suffix: None,
+            signing_fields_only: options.signing_fields_only,
+            rounding_mode: options.rounding_mode,
+        }),
+        definitions,
+    )?;
+    bytes.append(&mut end_marker("ObjectEndMarker", definitions)?);
+    Ok(STObject(bytes))
+}
+
+/// Serializes an array of inner objects, each wrapped in an object with the element's field name
+/// as its only key, e.g. `[{"Memo": {...}}]`, followed by the array end marker.
+fn serialize_array(
+    value: &serde_json::Value,
+    options: &SerializerOptions,
+    definitions: &Definitions,
+) -> Result<STArray> {
+    let elements = value
+        .as_array()
+        .ok_or_else(|| Error::Message(format!("expected an array of objects: {}", value)))?;
+    let mut bytes = Vec::new();
+    for element in elements {
+        let (name, object) = element
+            .as_object()
+            .filter(|element| element.len() == 1)
+            .and_then(|element| element.iter().next())
+            .ok_or_else(|| Error::Message(format!("expected a wrapped object: {}", element)))?;
+        let (field_code, type_code) = definitions.get_field_code_and_type_code(name)?;
+        bytes.append(&mut encode_field_id(type_code, field_code));
+        bytes.append(&mut serialize_object(object, options, definitions)?.0);
+    }
+    bytes.append(&mut end_marker("ArrayEndMarker", definitions)?);
+    Ok(STArray(bytes))
+}
+
+impl<'a, 'd> ser::Serializer for &'a mut Serializer<'d> {
     // The output type produced by this `Serializer` during successful
     // serialization. Most serializers that produce text or binary output should
     // set `Ok = ()` and serialize into an `io::Write` or buffer contained
@@ -296,10 +405,28 @@ impl<'a> ser::Serializer for &'a mut Serializer {
                         }
                     }
                 },
+                4 => *data = Value::Hash128(Hash128(v.to_owned())),
                 5 => *data = Value::Hash256(Hash256(v.to_owned())),
-                1 => {
-                    let i = get_transaction_type(v)?;
-                    *data = Value::Transaction(i as u16)
+                17 => *data = Value::Hash160(Hash160(v.to_owned())),
+                16 => {
+                    let i: u8 = v
+                        .parse()
+                        .map_err(|e| Error::InvalidAmount(e, v.to_owned()))?;
+                    *data = Value::UInt8(i);
+                }
+                // UInt16 fields given as names, such as TransactionType, are looked up.
+                1 => match v.parse() {
+                    Ok(i) => *data = Value::UInt16(i),
+                    Err(_) => {
+                        let i = self.definitions.get_transaction_type(v)?;
+                        *data = Value::Transaction(i as u16)
+                    }
+                },
+                2 => {
+                    let i: u32 = v
+                        .parse()
+                        .map_err(|e| Error::InvalidAmount(e, v.to_owned()))?;
+                    *data = Value::UInt32(i);
                 }
                 3 => {
                     let i: u64 = v
@@ -348,7 +475,8 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     // In Serde, unit means an anonymous value containing no data. Map this to
     // JSON as `null`.
     fn serialize_unit(self) -> Result<()> {
-        // self.output += "null";
+        // Unset fields are omitted, so the pending field must not receive the next value.
+        self.field = None;
         Ok(())
     }
 
@@ -489,7 +617,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 //
 // This impl is SerializeSeq so these methods are called after `serialize_seq`
 // is called on the Serializer.
-impl<'a> ser::SerializeSeq for &'a mut Serializer {
+impl<'a, 'd> ser::SerializeSeq for &'a mut Serializer<'d> {
     // Must match the `Ok` type of the serializer.
     type Ok = ();
     // Must match the `Error` type of the serializer.
@@ -514,7 +642,7 @@ impl<'a> ser::SerializeSeq for &'a mut Serializer {
 }
 
 // Same thing but for tuples.
-impl<'a> ser::SerializeTuple for &'a mut Serializer {
+impl<'a, 'd> ser::SerializeTuple for &'a mut Serializer<'d> {
     type Ok = ();
     type Error = Error;
 
@@ -535,7 +663,7 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
 }
 
 // Same thing but for tuple structs.
-impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
+impl<'a, 'd> ser::SerializeTupleStruct for &'a mut Serializer<'d> {
     type Ok = ();
     type Error = Error;
 
@@ -561,7 +689,7 @@ impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
 //
 // So the `end` method in this impl is responsible for closing both the `]` and
 // the `}`.
-impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
+impl<'a, 'd> ser::SerializeTupleVariant for &'a mut Serializer<'d> {
     type Ok = ();
     type Error = Error;
 
@@ -589,7 +717,7 @@ impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
 // `serialize_entry` method allows serializers to optimize for the case where
 // key and value are both available simultaneously. In JSON it doesn't make a
 // difference so the default behavior for `serialize_entry` is fine.
-impl<'a> ser::SerializeMap for &'a mut Serializer {
+impl<'a, 'd> ser::SerializeMap for &'a mut Serializer<'d> {
     type Ok = ();
     type Error = Error;
 
@@ -632,11 +760,18 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
                 _ => {}
             }
         }
-        if is_serialized_field(&key_str).unwrap_or_default() {
-            if self.options.signing_fields_only && !is_signing_field(&key_str).unwrap_or_default() {
+        let definitions = self.definitions;
+        if definitions
+            .is_serialized_field(&key_str)
+            .unwrap_or_default()
+        {
+            if self.options.signing_fields_only
+                && !definitions.is_signing_field(&key_str).unwrap_or_default()
+            {
+                self.field = None;
                 return Ok(());
             }
-            let (field_code, type_code) = get_field_code_and_type_code(&key_str)?;
+            let (field_code, type_code) = definitions.get_field_code_and_type_code(&key_str)?;
             self.field = Some((
                 FieldHeader {
                     type_code,
@@ -658,7 +793,37 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(&mut **self)
+        let header = match &self.field {
+            // The key is not serialized, so neither are the contents of its value.
+            None => return Ok(()),
+            Some((header, _)) => header.clone(),
+        };
+        // Inner objects and arrays are serialized separately, since their fields are sorted
+        // within the object rather than with the fields of the outer object. Path sets are
+        // nested arrays of steps, so are likewise collected as a whole.
+        let data = match header.type_code {
+            14 | 15 | 18 => {
+                let json =
+                    serde_json::to_value(value).map_err(|e| Error::Message(e.to_string()))?;
+                if json.is_null() {
+                    self.field = None;
+                    return Ok(());
+                }
+                match header.type_code {
+                    14 => {
+                        Value::STObject(serialize_object(&json, &self.options, self.definitions)?)
+                    }
+                    15 => Value::STArray(serialize_array(&json, &self.options, self.definitions)?),
+                    _ => Value::PathSet(
+                        serde_json::from_value(json).map_err(|e| Error::Message(e.to_string()))?,
+                    ),
+                }
+            }
+            _ => return value.serialize(&mut **self),
+        };
+        self.fields.push((header, data));
+        self.field = None;
+        Ok(())
     }
 
     fn end(self) -> Result<()> {
@@ -668,7 +833,7 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
 
 // Structs are like maps in which the keys are constrained to be compile-time
 // constant strings.
-impl<'a> ser::SerializeStruct for &'a mut Serializer {
+impl<'a, 'd> ser::SerializeStruct for &'a mut Serializer<'d> {
     type Ok = ();
     type Error = Error;
 
@@ -676,8 +841,9 @@ impl<'a> ser::SerializeStruct for &'a mut Serializer {
     where
         T: ?Sized + Serialize,
     {
-        if is_serialized_field(key).unwrap_or_default() {
-            let (field_code, type_code) = get_field_code_and_type_code(key)?;
+        let definitions = self.definitions;
+        if definitions.is_serialized_field(key).unwrap_or_default() {
+            let (field_code, type_code) = definitions.get_field_code_and_type_code(key)?;
             self.field = Some((
                 FieldHeader {
                     type_code,
@@ -687,7 +853,7 @@ impl<'a> ser::SerializeStruct for &'a mut Serializer {
                 Value::NotPresent,
             ));
             println!("{:?}", self.field);
-            return value.serialize(&mut **self);
+            return ser::SerializeMap::serialize_value(self, value);
         }
         Ok(())
     }
@@ -702,7 +868,7 @@ impl<'a> ser::SerializeStruct for &'a mut Serializer {
 
 // Similar to `SerializeTupleVariant`, here the `end` method is responsible for
 // closing both of the curly braces opened by `serialize_struct_variant`.
-impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
+impl<'a, 'd> ser::SerializeStructVariant for &'a mut Serializer<'d> {
     type Ok = ();
     type Error = Error;
 
@@ -754,6 +920,10 @@ fn test_example() {
 
 #[cfg(test)]
 mod tests {
+    use super::{end_marker, to_bytes, to_bytes_for_signing, to_bytes_with_definitions};
+    use crate::de::from_bytes;
+    use crate::definitions::{Definitions, Field, FieldInfo, DEFINITIONS};
+    use crate::error::Error;
     use serde::Deserialize;
     use serde_json::Value;
     #[derive(Deserialize)]
@@ -778,4 +948,201 @@ mod tests {
     //         );
     //     }
     // }
+
+    fn payment_with_memo() -> Value {
+        serde_json::json!({
+          "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+          "Amount": "1000000",
+          "Destination": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+          "Fee": "12",
+          "Flags": 0,
+          "Memos": [{
+            "Memo": {
+              "MemoType": "687474703A2F2F6578616D706C652E636F6D2F6D656D6F2F67656E65726963",
+              "MemoData": "72656E74"
+            }
+          }],
+          "Sequence": 1752792,
+          "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3",
+          "TransactionType": "Payment"
+        })
+    }
+
+    #[test]
+    fn test_memos() {
+        let expected = hex_literal::hex!("120000220000000024001ABED86140000000000F424068400000000000000C732103EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE38114DD76483FACDEE26E60D8A586BB58D09F27045C4683140A20B3C85F482532A9578DBB3950B85CA06594D1F9EA7C1F687474703A2F2F6578616D706C652E636F6D2F6D656D6F2F67656E657269637D0472656E74E1F1");
+        assert_eq!(to_bytes(&payment_with_memo()).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_numeric_strings() {
+        let account_set = |transfer_rate: Value, tick_size: Value, sequence: Value| {
+            serde_json::json!({
+              "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+              "Fee": "12",
+              "Flags": 0,
+              "Sequence": sequence,
+              "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3",
+              "TickSize": tick_size,
+              "TransactionType": "AccountSet",
+              "TransferRate": transfer_rate
+            })
+        };
+        let numbers = account_set(1002000000.into(), 5.into(), 1752792.into());
+        let strings = account_set("1002000000".into(), "5".into(), "1752792".into());
+        assert_eq!(to_bytes(&strings).unwrap(), to_bytes(&numbers).unwrap());
+        assert!(to_bytes(&account_set("1002000000".into(), "x".into(), "1".into())).is_err());
+    }
+
+    #[test]
+    fn test_fixed_length_hashes() {
+        let account_set = |email_hash: &str| {
+            serde_json::json!({
+              "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+              "EmailHash": email_hash,
+              "Fee": "12",
+              "Sequence": 1,
+              "TransactionType": "AccountSet"
+            })
+        };
+        let email_hash = "98B4375E1D753E5B91627516F6D70977";
+        let bytes = to_bytes(&account_set(email_hash)).unwrap();
+        let expected = [vec![0x41], hex::decode(email_hash).unwrap()].concat();
+        assert!(bytes.windows(17).any(|w| w == expected));
+        // Hashes that are too short, too long or not hex are rejected.
+        for invalid in [&email_hash[2..], &format!("{}00", email_hash), "not hex"] {
+            assert_eq!(
+                to_bytes(&account_set(invalid)),
+                Err(Error::InvalidHash(invalid.to_owned()))
+            );
+        }
+        let currency = "0000000000000000000000005553440000000000";
+        let bytes = to_bytes(&serde_json::json!({ "TakerPaysCurrency": currency })).unwrap();
+        assert_eq!(
+            bytes,
+            [vec![0x01, 0x11], hex::decode(currency).unwrap()].concat()
+        );
+        assert!(to_bytes(&serde_json::json!({ "TakerPaysCurrency": &currency[2..] })).is_err());
+    }
+
+    #[test]
+    fn test_path_set() {
+        let paths = serde_json::json!({
+          "Paths": [
+            [
+              { "account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys" },
+              { "currency": "USD", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B" }
+            ],
+            [{ "currency": "XRP" }]
+          ]
+        });
+        let expected = hex_literal::hex!("0112 01 DD76483FACDEE26E60D8A586BB58D09F27045C46 30 0000000000000000000000005553440000000000 0A20B3C85F482532A9578DBB3950B85CA06594D1 FF 10 0000000000000000000000000000000000000000 00");
+        let bytes = to_bytes(&paths).unwrap();
+        assert_eq!(bytes, expected);
+        assert_eq!(from_bytes(&bytes).unwrap(), paths);
+        let invalid = serde_json::json!({ "Paths": [[{ "account": "not an address" }]] });
+        assert_eq!(to_bytes(&invalid), Err(Error::InvalidAddress));
+    }
+
+    #[test]
+    fn test_path_set_fixture() {
+        // The PathSet fixture from xrpl.js' ripple-binary-codec binary serializer tests.
+        let btc = |account: &str| {
+            serde_json::json!({
+              "account": account,
+              "currency": "BTC",
+              "issuer": account
+            })
+        };
+        let usd = serde_json::json!({
+          "currency": "USD",
+          "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B"
+        });
+        let paths = serde_json::json!({
+          "Paths": [
+            [
+              btc("r9hEDb4xBGRfBCcX3E4FirDWQBAYtpxC8K"),
+              btc("rM1oqKtfh1zgjdAgbFmaRm3btfGBX25xVo"),
+              btc("rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B"),
+              usd
+            ],
+            [
+              btc("r9hEDb4xBGRfBCcX3E4FirDWQBAYtpxC8K"),
+              btc("rM1oqKtfh1zgjdAgbFmaRm3btfGBX25xVo"),
+              btc("rpvfJ4mR6QQAeogpXEKnuyGBx8mYCSnYZi"),
+              usd
+            ],
+            [
+              btc("r9hEDb4xBGRfBCcX3E4FirDWQBAYtpxC8K"),
+              btc("r3AWbdp2jQLXLywJypdoNwVSvr81xs3uhn"),
+              { "currency": "XRP" },
+              usd
+            ]
+          ]
+        });
+        let expected = hex_literal::hex!("0112 31585E1F3BD02A15D6185F8BB9B57CC60DEDDB37C10000000000000000000000004254430000000000585E1F3BD02A15D6185F8BB9B57CC60DEDDB37C131E4FE687C90257D3D2D694C8531CDEECBE84F33670000000000000000000000004254430000000000E4FE687C90257D3D2D694C8531CDEECBE84F3367310A20B3C85F482532A9578DBB3950B85CA06594D100000000000000000000000042544300000000000A20B3C85F482532A9578DBB3950B85CA06594D13000000000000000000000000055534400000000000A20B3C85F482532A9578DBB3950B85CA06594D1FF31585E1F3BD02A15D6185F8BB9B57CC60DEDDB37C10000000000000000000000004254430000000000585E1F3BD02A15D6185F8BB9B57CC60DEDDB37C131E4FE687C90257D3D2D694C8531CDEECBE84F33670000000000000000000000004254430000000000E4FE687C90257D3D2D694C8531CDEECBE84F33673115036E2D3F5437A83E5AC3CAEE34FF2C21DEB618000000000000000000000000425443000000000015036E2D3F5437A83E5AC3CAEE34FF2C21DEB6183000000000000000000000000055534400000000000A20B3C85F482532A9578DBB3950B85CA06594D1FF31585E1F3BD02A15D6185F8BB9B57CC60DEDDB37C10000000000000000000000004254430000000000585E1F3BD02A15D6185F8BB9B57CC60DEDDB37C13157180C769B66D942EE69E6DCC940CA48D82337AD000000000000000000000000425443000000000057180C769B66D942EE69E6DCC940CA48D82337AD1000000000000000000000000000000000000000003000000000000000000000000055534400000000000A20B3C85F482532A9578DBB3950B85CA06594D100");
+        let bytes = to_bytes(&paths).unwrap();
+        assert_eq!(bytes, expected);
+        assert_eq!(from_bytes(&bytes).unwrap(), paths);
+    }
+
+    #[test]
+    fn test_end_markers() {
+        assert_eq!(end_marker("ObjectEndMarker", &DEFINITIONS).unwrap(), [0xE1]);
+        assert_eq!(end_marker("ArrayEndMarker", &DEFINITIONS).unwrap(), [0xF1]);
+        // The only memo is terminated by the object end marker, then the array by its own.
+        let bytes = to_bytes(&payment_with_memo()).unwrap();
+        assert!(bytes.ends_with(&[0xE1, 0xF1]));
+    }
+
+    #[test]
+    fn test_signers_not_signed() {
+        let mut tx = payment_with_memo();
+        let unsigned = to_bytes_for_signing(&tx).unwrap();
+        tx["Signers"] = serde_json::json!([{
+          "Signer": {
+            "Account": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+            "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3",
+            "TxnSignature": "3044"
+          }
+        }]);
+        assert_eq!(to_bytes_for_signing(&tx).unwrap(), unsigned);
+        assert!(to_bytes(&tx).unwrap().len() > to_bytes(&payment_with_memo()).unwrap().len());
+        // Unset optional arrays are omitted.
+        tx["Signers"] = Value::Null;
+        assert_eq!(to_bytes_for_signing(&tx).unwrap(), unsigned);
+    }
+
+    #[test]
+    fn test_null_fields_omitted() {
+        let mut tx = payment_with_memo();
+        let expected = to_bytes(&tx).unwrap();
+        // The fields after an unset amount must not be taken as the fields of that amount.
+        tx["DeliverMin"] = Value::Null;
+        tx["SendMax"] = Value::Null;
+        tx["DestinationTag"] = Value::Null;
+        assert_eq!(to_bytes(&tx).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_runtime_definitions() {
+        let mut definitions = Definitions::from_json(include_str!("definitions.json")).unwrap();
+        definitions.fields.push(Field(
+            "NewAmendmentField".to_owned(),
+            FieldInfo {
+                nth: 99,
+                is_vl_encoded: false,
+                is_serialized: true,
+                is_signing_field: true,
+                r#type: "UInt32".to_owned(),
+            },
+        ));
+        let value = serde_json::json!({ "NewAmendmentField": 7 });
+        // Unknown to the bundled definitions, so it is not serialized.
+        assert!(to_bytes(&value).unwrap().is_empty());
+        assert_eq!(
+            to_bytes_with_definitions(&value, None, &definitions).unwrap(),
+            [0x20, 99, 0, 0, 0, 7]
+        );
+    }
 }
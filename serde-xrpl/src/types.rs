@@ -1,6 +1,9 @@
 use crate::error::{Error, Result};
-use crate::utils::{decode_base58, encode_issued_currency_amount, encode_variable_length};
-use std::collections::HashMap;
+use crate::utils::{
+    decode_base58, encode_currency_code, encode_issued_currency_amount_with_rounding,
+    encode_variable_length, RoundingMode,
+};
+use serde::Deserialize;
 
 pub enum Field {}
 
@@ -31,7 +34,7 @@ type_code!(STObject, 14);
 // type_code!(Unknown, -2);
 // type_code!(Transaction, 10001);
 type_code!(Hash160, 17);
-// type_code!(PathSet, 18);
+type_code!(PathSet, 18);
 // type_code!(LedgerEntry, 10002);
 type_code!(u16, 1);
 // type_code!(NotPresent, 0);
@@ -41,7 +44,7 @@ type_code!(STArray, 15);
 
 #[derive(Debug, Clone)]
 pub enum Value {
-    Hash128(String),
+    Hash128(Hash128),
     Blob(Blob),
     AccountID(String),
     Amount(Amount),
@@ -52,24 +55,29 @@ pub enum Value {
     Unknown,
     Transaction(u16),
     Hash160(Hash160),
-    PathSet,
+    PathSet(PathSet),
     LedgerEntry,
     UInt16(u16),
     NotPresent,
     UInt64(u64),
     UInt32(u32),
-    STArray(Vec<Value>),
+    STArray(STArray),
 }
 
 impl Value {
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        self.to_bytes_with_rounding(RoundingMode::default())
+    }
+
+    /// Encodes the value, rounding any issued currency amounts with the provided rounding mode.
+    pub fn to_bytes_with_rounding(&self, rounding_mode: RoundingMode) -> Result<Vec<u8>> {
         match self {
             Self::AccountID(account_id) => {
                 let address = decode_base58(account_id, &[0x0])?;
                 let length = encode_variable_length(address.len());
                 Ok([length, address].concat())
             }
-            Self::Amount(amount) => amount.to_bytes(),
+            Self::Amount(amount) => amount.to_bytes_with_rounding(rounding_mode),
             Self::UInt8(u) => Ok(u.to_be_bytes().to_vec()),
             Self::UInt16(u) => Ok(u.to_be_bytes().to_vec()),
             Self::UInt32(u) => Ok(u.to_be_bytes().to_vec()),
@@ -80,7 +88,12 @@ impl Value {
                 Ok([length, data].concat())
             }
             Self::Transaction(tx) => Ok(tx.to_be_bytes().to_vec()),
+            Self::Hash128(hash) => hash.to_bytes(),
+            Self::Hash160(hash) => hash.to_bytes(),
             Self::Hash256(hash) => Ok(hash.to_bytes().to_vec()),
+            Self::STObject(object) => Ok(object.0.clone()),
+            Self::STArray(array) => Ok(array.0.clone()),
+            Self::PathSet(path_set) => path_set.to_bytes(),
             Self::Vector256(v) => {
                 let data: Vec<u8> =
                     v.0.iter()
@@ -100,9 +113,29 @@ impl Value {
 #[derive(Debug, Clone)]
 pub struct Hash160(pub String);
 
+impl Hash160 {
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        decode_hash(&self.0, 20)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Hash128(pub String);
 
+impl Hash128 {
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        decode_hash(&self.0, 16)
+    }
+}
+
+// Decodes a hex encoded hash, which must be exactly `length` bytes.
+fn decode_hash(hash: &str, length: usize) -> Result<Vec<u8>> {
+    match hex::decode(hash) {
+        Ok(bytes) if bytes.len() == length => Ok(bytes),
+        _ => Err(Error::InvalidHash(hash.to_owned())),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Hash256(pub String);
 
@@ -138,25 +171,100 @@ pub enum Amount {
 
 impl Amount {
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        self.to_bytes_with_rounding(RoundingMode::default())
+    }
+
+    pub fn to_bytes_with_rounding(&self, rounding_mode: RoundingMode) -> Result<Vec<u8>> {
         match self {
             Self::XRP(amount) => Ok((amount | 0x4000000000000000u64).to_be_bytes().to_vec()),
             Self::IssuedCurrency {
                 value,
                 currency,
                 issuer,
-            } => encode_issued_currency_amount(&value, &currency, &issuer),
+            } => encode_issued_currency_amount_with_rounding(
+                &value,
+                &currency,
+                &issuer,
+                rounding_mode,
+            ),
         }
     }
 }
 
+/// An inner object, holding its fields already serialized in canonical order and terminated by the
+/// `ObjectEndMarker` field.
 #[derive(Debug, Clone)]
-pub struct STObject(HashMap<String, Value>);
+pub struct STObject(pub Vec<u8>);
 
 #[derive(Debug, Clone)]
 pub struct Blob(pub String);
 
+/// An array of inner objects, holding the field id and serialized object of each element,
+/// terminated by the `ArrayEndMarker` field.
 #[derive(Debug, Clone)]
-pub struct STArray(Vec<Value>);
+pub struct STArray(pub Vec<u8>);
 
 #[derive(Debug, Clone)]
 pub struct Vector256(pub Vec<Hash256>);
+
+pub(crate) const PATH_SEPARATOR: u8 = 0xFF;
+pub(crate) const PATHSET_END: u8 = 0x00;
+pub(crate) const PATH_STEP_ACCOUNT: u8 = 0x01;
+pub(crate) const PATH_STEP_CURRENCY: u8 = 0x10;
+pub(crate) const PATH_STEP_ISSUER: u8 = 0x20;
+
+/// A step of a payment path, rippling through an account or converting through an order book.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PathStep {
+    pub account: Option<String>,
+    pub currency: Option<String>,
+    pub issuer: Option<String>,
+}
+
+impl PathStep {
+    /// Encodes the step as a type byte, flagging which fields are present, followed by each of
+    /// the present fields.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut step_type = 0;
+        let mut bytes = Vec::new();
+        if let Some(account) = &self.account {
+            step_type |= PATH_STEP_ACCOUNT;
+            bytes.extend(AccountID::from_str(account)?.0);
+        }
+        if let Some(currency) = &self.currency {
+            step_type |= PATH_STEP_CURRENCY;
+            // XRP is represented by the all zero currency code within paths.
+            match currency.as_str() {
+                "XRP" => bytes.extend([0u8; 20]),
+                _ => bytes.append(&mut encode_currency_code(currency)?),
+            }
+        }
+        if let Some(issuer) = &self.issuer {
+            step_type |= PATH_STEP_ISSUER;
+            bytes.extend(AccountID::from_str(issuer)?.0);
+        }
+        Ok([vec![step_type], bytes].concat())
+    }
+}
+
+/// A set of alternative payment paths, each a sequence of steps.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PathSet(pub Vec<Vec<PathStep>>);
+
+impl PathSet {
+    /// Encodes the steps of each path, separating the paths with `0xFF` and terminating the set
+    /// with `0x00`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        for (i, path) in self.0.iter().enumerate() {
+            if i > 0 {
+                bytes.push(PATH_SEPARATOR);
+            }
+            for step in path {
+                bytes.append(&mut step.to_bytes()?);
+            }
+        }
+        bytes.push(PATHSET_END);
+        Ok(bytes)
+    }
+}
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use super::error::{Error, Result};
 use bs58::Alphabet;
-use rust_decimal::{prelude::ToPrimitive, Decimal, MathematicalOps};
+use rust_decimal::{prelude::ToPrimitive, Decimal, MathematicalOps, RoundingStrategy};
 use serde::{ser, Serialize};
 
 pub fn encode_variable_length(length: usize) -> Vec<u8> {
@@ -53,30 +53,86 @@ pub fn decode_base58(b58_string: &str, prefix: &[u8]) -> Result<Vec<u8>> {
     }
 }
 
-pub fn encode_currency_code(currency_code: &str) -> Vec<u8> {
+/// Encodes a currency code, either a standard 3 character code or a 40 character hex code, as
+/// its 160-bit representation.
+pub fn encode_currency_code(currency_code: &str) -> Result<Vec<u8>> {
     if currency_code.as_bytes().len() == 3 {
-        return [
+        return Ok([
             [0u8; 12].to_vec(),
             currency_code.as_bytes().to_vec(),
             [0u8; 5].to_vec(),
         ]
-        .concat();
+        .concat());
     }
     if currency_code.as_bytes().len() == 40 {
-        return hex::decode(&currency_code).unwrap()
+        return hex::decode(&currency_code)
+            .map_err(|_| Error::InvalidCurrencyCode(currency_code.to_owned()));
     }
-    panic!("invalid currency code with length: {}", currency_code.as_bytes().len())
+    Err(Error::InvalidCurrencyCode(currency_code.to_owned()))
+}
+
+/// The maximum number of significant digits that an issued currency amount can hold.
+pub const ISSUED_CURRENCY_PRECISION: u32 = 16;
+
+/// The rounding applied to an issued currency amount that has more significant digits than
+/// [`ISSUED_CURRENCY_PRECISION`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Truncate the extra digits, rounding towards zero.
+    Down,
+    /// Round to the nearest representable value, rounding midpoints away from zero.
+    HalfUp,
+}
+
+impl Default for RoundingMode {
+    /// Defaults to `Down`, since rounding a payment amount up could spend more than the caller
+    /// asked for.
+    fn default() -> Self {
+        Self::Down
+    }
+}
+
+impl RoundingMode {
+    fn strategy(&self) -> RoundingStrategy {
+        match self {
+            Self::Down => RoundingStrategy::ToZero,
+            Self::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+        }
+    }
+}
+
+/// Rounds an issued currency amount to the number of significant digits that the XRPL can
+/// represent, using the provided rounding mode.
+pub fn round_issued_currency_amount(amount: Decimal, rounding_mode: RoundingMode) -> Decimal {
+    if amount.is_zero() {
+        return amount;
+    }
+    let e = amount.abs().log10().floor().to_i32().unwrap_or_default();
+    let dp = (ISSUED_CURRENCY_PRECISION as i32 - 1 - e).max(0) as u32;
+    amount.round_dp_with_strategy(dp, rounding_mode.strategy())
 }
 
 pub fn encode_issued_currency_amount(
     amount: &str,
     currency: &str,
     issuer: &str,
+) -> Result<Vec<u8>> {
+    encode_issued_currency_amount_with_rounding(amount, currency, issuer, RoundingMode::default())
+}
+
+pub fn encode_issued_currency_amount_with_rounding(
+    amount: &str,
+    currency: &str,
+    issuer: &str,
+    rounding_mode: RoundingMode,
 ) -> Result<Vec<u8>> {
     let encoded_address = decode_base58(issuer, &[0x00])?;
 
-    let mut decimal_amount = Decimal::from_str(amount)
-        .map_err(|e| Error::InvalidIssuedCurrencyAmount(format!("{:?}", e)))?;
+    let mut decimal_amount = round_issued_currency_amount(
+        Decimal::from_str(amount)
+            .map_err(|e| Error::InvalidIssuedCurrencyAmount(format!("{:?}", e)))?,
+        rounding_mode,
+    );
 
     let mut encoded_amount;
 
@@ -98,7 +154,7 @@ pub fn encode_issued_currency_amount(
         encoded_amount[1] |= (exponent_bytes & 0x03) << 6u8;
     }
 
-    let encoded_currency = encode_currency_code(currency);
+    let encoded_currency = encode_currency_code(currency)?;
 
     Ok([encoded_amount.to_vec(), encoded_currency, encoded_address]
         .concat()
@@ -502,3 +558,38 @@ impl<'a> ser::SerializeStructVariant for &'a mut StringSerializer {
         unimplemented!()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_currency_code, round_issued_currency_amount, RoundingMode};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_round_issued_currency_amount() {
+        let amount = Decimal::from_str("1.23456789012345678").unwrap();
+        assert_eq!(
+            round_issued_currency_amount(amount, RoundingMode::Down),
+            Decimal::from_str("1.234567890123456").unwrap()
+        );
+        assert_eq!(
+            round_issued_currency_amount(amount, RoundingMode::HalfUp),
+            Decimal::from_str("1.234567890123457").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_encode_currency_code() {
+        assert_eq!(
+            encode_currency_code("USD").unwrap(),
+            hex_literal::hex!("0000000000000000000000005553440000000000").to_vec()
+        );
+        assert_eq!(
+            encode_currency_code("0158415500000000C1F76FF6ECB0BAC600000000").unwrap(),
+            hex_literal::hex!("0158415500000000C1F76FF6ECB0BAC600000000").to_vec()
+        );
+        assert!(encode_currency_code("US").is_err());
+        assert!(encode_currency_code("0158415500000000C1F76FF6ECB0BAC6000000").is_err());
+        assert!(encode_currency_code("Z158415500000000C1F76FF6ECB0BAC600000000").is_err());
+    }
+}
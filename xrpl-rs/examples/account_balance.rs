@@ -21,10 +21,8 @@ async fn main() {
     // let mut wallet = Wallet::from_secret(&creds.account.secret).unwrap();
     // println!("{}", wallet.address());
 
-    // Create an account info request.
-    let mut req = AccountInfoRequest::default();
-    // Set the account to the testnet credentials.
-    req.account = "rpD1ocF4rs3crXBjgdco84KhGQGep589YR".to_owned();
+    // Create an account info request for the testnet credentials.
+    let req = AccountInfoRequest::new("rpD1ocF4rs3crXBjgdco84KhGQGep589YR".parse().unwrap());
     // Fetch the account info for an address.
     let account_info = xrpl.account_info(req).await.unwrap();
     // Print the account and balance
@@ -53,10 +53,9 @@ async fn main() {
         .expect("failed to make submit request");
     println!("Got response to submit request: {:?}", submit_res);
 
-    // Create an account info request to see the balance of account two.
-    let mut req = AccountInfoRequest::default();
-    // Set the account to the second set of testnet credentials.
-    req.account = "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned();
+    // Create an account info request to see the balance of account two, using the second set of
+    // testnet credentials.
+    let req = AccountInfoRequest::new("rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".parse().unwrap());
     // Fetch the account info for an address.
     let account_info = xrpl
         .account_info(req)
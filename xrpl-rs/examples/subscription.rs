@@ -18,7 +18,7 @@ async fn main() {
     );
     // Subscribe to ledger events.
     let ledgers = xrpl
-        .subscribe(SubscribeRequest::Streams(vec!["ledger".to_owned()]))
+        .subscribe(SubscribeRequest::streams(vec!["ledger".to_owned()]))
         .await
         .unwrap();
     // Print each ledger event as it comes through.
@@ -28,6 +28,7 @@ async fn main() {
                 Ok(SubscriptionEvent::LedgerClosed(ledger_closed)) => {
                     println!("{}", ledger_closed.ledger_hash);
                 }
+                Ok(_) => {}
                 Err(e) => {
                     println!("error: {:?}", e);
                 }
@@ -15,8 +15,7 @@
 //!         .unwrap());
 //!
 //! // Create a request
-//! let mut req = AccountInfoRequest::default();
-//! req.account = "rG1QQv2nh2gr7RCZ1P8YYcBUKCCN633jCn".to_owned();
+//! let req = AccountInfoRequest::new("rG1QQv2nh2gr7RCZ1P8YYcBUKCCN633jCn".parse().unwrap());
 //!
 //! // Fetch the account info for an address.
 //! let account_info = block_on(async {
@@ -29,25 +28,46 @@
 //! assert_eq!(account_info.account_data.balance, CurrencyAmount::xrp(9977));
 //! ```
 
+use std::collections::{HashSet, VecDeque};
 use std::pin::Pin;
 
-use futures::stream::Stream;
+use futures::stream::{Stream, StreamExt};
 use serde::de::DeserializeOwned;
+use serde_xrpl::definitions::Definitions;
+use transaction::types::Transaction;
 use transports::{DuplexTransport, Transport, TransportError};
 use types::{
     account::{
         AccountChannelsRequest, AccountChannelsResponse, AccountCurrenciesRequest,
         AccountCurrenciesResponse, AccountInfoRequest, AccountInfoResponse, AccountLinesRequest,
-        AccountLinesResponse, AccountOfferRequest, AccountOfferResponse,
+        AccountLinesResponse, AccountObjectsRequest, AccountObjectsResponse, AccountOfferRequest,
+        AccountOfferResponse, AccountSummary, AccountTXRequest, AccountTXResponse,
+        AccountTrustLine,
     },
+    amendments::{FeatureRequest, FeatureResponse},
+    book::{BookOffersRequest, BookOffersResponse},
     channels::{ChannelVerifyRequest, ChannelVerifyResponse},
     fee::{FeeRequest, FeeResponse},
-    ledger::{LedgerRequest, LedgerResponse},
-    submit::{SignAndSubmitRequest, SubmitRequest, SubmitResponse},
+    gateway::{GatewayBalancesRequest, GatewayBalancesResponse},
+    ledger::{
+        LedgerDataRequest, LedgerDataResponse, LedgerEntryRequest, LedgerEntryResponse,
+        LedgerRequest, LedgerRequestIndex, LedgerResponse,
+    },
+    path::{PathFindRequest, PathFindResponse, RipplePathFindRequest, RipplePathFindResponse},
+    server::{
+        ServerDefinitionsRequest, ServerInfoRequest, ServerInfoResponse, ServerStateRequest,
+        ServerStateResponse,
+    },
+    submit::{
+        EngineResult, EngineResultCategory, SignAndSubmitRequest, SimulateRequest,
+        SimulateResponse, SubmitRequest, SubmitResponse,
+    },
     subscribe::{SubscribeRequest, SubscriptionEvent},
-    tx::{TxRequest, TxResponse},
+    tx::{minted_nftoken_id, Outcome, TransactionMetadata, TxRequest, TxResponse},
+    ApiErrorCode, BigInt, ClassicAddress, CurrencyAmount, ErrorResponse, Hash256,
     TransactionEntryRequest, TransactionEntryResponse,
 };
+use wallet::Wallet;
 
 pub mod transaction;
 pub mod transports;
@@ -55,18 +75,109 @@ pub mod types;
 pub mod utils;
 pub mod wallet;
 
-/// An enum providing error types that can be returned when calling XRPL methods.
+/// An enum providing error types that can be returned when calling XRPL methods, signing
+/// transactions or serializing them, so that errors from each layer can be propagated with `?`.
 #[derive(Debug)]
 pub enum Error {
-    TransportError(TransportError),
+    /// The request could not be sent, or its response could not be received or parsed.
+    Transport(TransportError),
+    /// A wallet failed to prepare or sign a transaction.
+    Wallet(Box<wallet::Error>),
+    /// A value could not be serialized in the XRPL binary format.
+    Serialization(serde_xrpl::error::Error),
+    /// An input was rejected before it was sent to the server.
+    Validation(ValidationError),
+    /// The server returned an error, or a response that could not be used.
+    Api(ApiError),
+}
+
+#[derive(Debug)]
+pub enum ValidationError {
+    InvalidHash(String),
+    InvalidAddress(String),
+    InvalidAmount(String),
+    InvalidCurrencyCode(String),
+    /// The address is for a different network than the one the transaction is for.
+    WrongNetwork(String),
+}
+
+#[derive(Debug)]
+pub enum ApiError {
+    /// The error response returned by the server, e.g. `actNotFound`.
+    Response(ErrorResponse),
+    /// The response was missing a value or had a value of an unexpected type.
+    UnexpectedResponse(&'static str),
+    /// The submitted transaction was rejected, e.g. `temMALFORMED`, so it can never be validated.
+    TransactionRejected(EngineResult),
+    /// The submitted transaction was not validated by its LastLedgerSequence, so it never will be.
+    TransactionExpired,
+    /// The submitted transaction was validated with a result other than tesSUCCESS, e.g.
+    /// `tecNO_PERMISSION`, so it only charged its fee.
+    TransactionFailed(EngineResult),
 }
 
 impl From<TransportError> for Error {
     fn from(e: TransportError) -> Self {
-        Self::TransportError(e)
+        match e {
+            TransportError::APIError(e) => Self::Api(ApiError::Response(e)),
+            e => Self::Transport(e),
+        }
+    }
+}
+
+impl From<wallet::Error> for Error {
+    fn from(e: wallet::Error) -> Self {
+        match e {
+            wallet::Error::XRPLError(e) => e,
+            e => Self::Wallet(Box::new(e)),
+        }
+    }
+}
+
+impl From<serde_xrpl::error::Error> for Error {
+    fn from(e: serde_xrpl::error::Error) -> Self {
+        Self::Serialization(e)
     }
 }
 
+impl From<ValidationError> for Error {
+    fn from(e: ValidationError) -> Self {
+        Self::Validation(e)
+    }
+}
+
+impl From<ApiError> for Error {
+    fn from(e: ApiError) -> Self {
+        Self::Api(e)
+    }
+}
+
+fn recommended_fee_drops(fee: &FeeResponse) -> Result<u64, Error> {
+    let open_ledger_fee = match &fee.drops.open_ledger_fee {
+        CurrencyAmount::XRP(drops) => drops.0,
+        _ => return Err(ApiError::UnexpectedResponse("open ledger fee is not XRP").into()),
+    };
+    let base_fee = match &fee.drops.base_fee {
+        Some(CurrencyAmount::XRP(drops)) => drops.0,
+        _ => 0,
+    };
+    let open_ledger_fee = open_ledger_fee.max(base_fee);
+    match (&fee.current_queue_size, &fee.max_queue_size) {
+        (Some(current), Some(max)) if max.0 > 0 => {
+            let queued = current.0.min(max.0) as u64;
+            Ok(open_ledger_fee
+                .saturating_add(open_ledger_fee.saturating_mul(queued) / max.0 as u64))
+        }
+        _ => Ok(open_ledger_fee),
+    }
+}
+
+/// The number of recent transaction hashes remembered when deduplicating validated transactions.
+const MAX_SEEN_TRANSACTIONS: usize = 1000;
+
+/// How long to wait between checks for the outcome of a submitted transaction.
+const OUTCOME_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 /// A client that exposes methods for interacting with the XRP Ledger.
 ///
 /// # Examples
@@ -84,8 +195,7 @@ impl From<TransportError> for Error {
 ///         .unwrap());
 ///
 /// // Create a request
-/// let mut req = AccountInfoRequest::default();
-/// req.account = "rG1QQv2nh2gr7RCZ1P8YYcBUKCCN633jCn".to_owned();
+/// let req = AccountInfoRequest::new("rG1QQv2nh2gr7RCZ1P8YYcBUKCCN633jCn".parse().unwrap());
 ///
 /// // Fetch the account info for an address.
 /// let account_info = block_on(async {
@@ -117,6 +227,11 @@ impl<T: Transport> XRPL<T> {
     pub fn new(transport: T) -> Self {
         Self { transport }
     }
+    /// Returns whether the configured transport supports subscriptions, so that code generic over
+    /// the transport can fall back to polling when it does not.
+    pub fn can_subscribe(&self) -> bool {
+        self.transport.supports_subscriptions()
+    }
     impl_rpc_method!(
         /// The account_channels method returns information about an account's Payment Channels. This includes only channels where the specified account is the channel's source, not the destination. (A channel's "source" and "owner" are the same.) All information retrieved is relative to a particular version of the ledger.
         account_channels,
@@ -145,6 +260,20 @@ impl<T: Transport> XRPL<T> {
         AccountLinesRequest,
         AccountLinesResponse
     );
+    impl_rpc_method!(
+        /// The account_objects command returns the raw ledger format for all objects owned by an account. For a higher-level view of an account's trust lines and balances, see the account_lines method instead.
+        account_objects,
+        "account_objects",
+        AccountObjectsRequest,
+        AccountObjectsResponse
+    );
+    impl_rpc_method!(
+        /// The account_tx method retrieves a list of validated transactions that involve a given account.
+        account_tx,
+        "account_tx",
+        AccountTXRequest,
+        AccountTXResponse
+    );
     impl_rpc_method!(
         /// The account_offers method retrieves a list of offers made by a given account that are outstanding as of a particular ledger version.
         account_offers,
@@ -152,6 +281,27 @@ impl<T: Transport> XRPL<T> {
         AccountOfferRequest,
         AccountOfferResponse
     );
+    impl_rpc_method!(
+        /// The book_offers method retrieves a list of offers, also known as the order book, between two currencies.
+        book_offers,
+        "book_offers",
+        BookOffersRequest,
+        BookOffersResponse
+    );
+    impl_rpc_method!(
+        /// The ripple_path_find method is a simplified version of the path_find method that provides a single response with a payment path you can use right away.
+        ripple_path_find,
+        "ripple_path_find",
+        RipplePathFindRequest,
+        RipplePathFindResponse
+    );
+    impl_rpc_method!(
+        /// The gateway_balances command calculates the total balances issued by a given account, optionally excluding amounts held by operational addresses.
+        gateway_balances,
+        "gateway_balances",
+        GatewayBalancesRequest,
+        GatewayBalancesResponse
+    );
     impl_rpc_method!(
         /// The transaction_entry method retrieves information on a single transaction from a specific ledger version. (The tx method, by contrast, searches all ledgers for the specified transaction. We recommend using that method instead.)
         transaction_entry,
@@ -187,6 +337,20 @@ impl<T: Transport> XRPL<T> {
         LedgerRequest,
         LedgerResponse
     );
+    impl_rpc_method!(
+        /// The ledger_data method retrieves contents of the specified ledger. You can iterate through several calls to retrieve the entire contents of a single ledger version.
+        ledger_data,
+        "ledger_data",
+        LedgerDataRequest,
+        LedgerDataResponse
+    );
+    impl_rpc_method!(
+        /// The ledger_entry method returns a single ledger object from the XRP Ledger in its raw format.
+        ledger_entry,
+        "ledger_entry",
+        LedgerEntryRequest,
+        LedgerEntryResponse
+    );
     impl_rpc_method!(
         /// The channel_verify method checks the validity of a signature that can be used to redeem a specific amount of XRP from a payment channel.
         channel_verify,
@@ -201,31 +365,480 @@ impl<T: Transport> XRPL<T> {
         TxRequest,
         TxResponse
     );
+    impl_rpc_method!(
+        /// The simulate method applies a transaction against the current open ledger without submitting it to the network, returning the result and the metadata it would produce.
+        simulate,
+        "simulate",
+        SimulateRequest,
+        SimulateResponse
+    );
+    impl_rpc_method!(
+        /// The feature command returns information about amendments this server knows about, including whether they are enabled and whether the server knows how to apply the amendments.
+        feature,
+        "feature",
+        FeatureRequest,
+        FeatureResponse
+    );
+    impl_rpc_method!(
+        /// The server_definitions command returns the field, type and transaction definitions the server uses for the binary format, along with their hash. Passing them to `serde_xrpl::ser::to_bytes_with_definitions` serializes transactions exactly as the connected server expects, including fields added by amendments newer than the bundled definitions.
+        server_definitions,
+        "server_definitions",
+        ServerDefinitionsRequest,
+        Definitions
+    );
+    impl_rpc_method!(
+        /// The server_info command asks the server for a human-readable version of various information about the rippled server being queried.
+        server_info,
+        "server_info",
+        ServerInfoRequest,
+        ServerInfoResponse
+    );
+    impl_rpc_method!(
+        /// The server_state command asks the server for various machine-readable information about the rippled server's current state.
+        server_state,
+        "server_state",
+        ServerStateRequest,
+        ServerStateResponse
+    );
+    /// Approximates a dry-run of a signed transaction by submitting it with `fail_hard` set and
+    /// returning only the preliminary `engine_result`.
+    ///
+    /// NOTE `submit` has no true dry-run. `fail_hard` only prevents a transaction that fails
+    /// locally from being retried or relayed; a transaction that applies successfully to the open
+    /// ledger is still relayed to the network and may be included in a validated ledger. Prefer
+    /// [`XRPL::simulate`] against servers that support it.
+    pub async fn dry_run(&self, tx_blob: &str) -> Result<String, Error> {
+        let res = self
+            .submit(SubmitRequest {
+                tx_blob: tx_blob.to_owned(),
+                fail_hard: Some(true),
+            })
+            .await?;
+        Ok(res.engine_result)
+    }
+    /// Returns a fee, in drops, that should get a transaction into the current open ledger.
+    ///
+    /// The open ledger cost is increased in proportion to how full the transaction queue is, up to
+    /// double the open ledger cost when the queue is full, so that the fee stays competitive as
+    /// the queue fills up.
+    pub async fn recommended_fee(&self) -> Result<BigInt, Error> {
+        let fee = self.fee(FeeRequest::default()).await?;
+        Ok(BigInt(recommended_fee_drops(&fee)?))
+    }
+    /// Returns a more aggressive fee, in drops, for urgent transactions. This is double the
+    /// recommended fee, or the median fee of the previous ledger if that is higher.
+    pub async fn urgent_fee(&self) -> Result<BigInt, Error> {
+        let fee = self.fee(FeeRequest::default()).await?;
+        let recommended = recommended_fee_drops(&fee)?;
+        let median = match &fee.drops.median_fee {
+            Some(CurrencyAmount::XRP(drops)) => drops.0,
+            _ => 0,
+        };
+        Ok(BigInt(recommended.saturating_mul(2).max(median)))
+    }
+    /// Returns whether the account owns no objects that would block it from being deleted. An
+    /// AccountDelete transaction for an account with blocking objects fails with
+    /// `tecHAS_OBLIGATIONS`.
+    pub async fn can_delete_account(&self, account: &str) -> Result<bool, Error> {
+        let res = self
+            .account_objects(AccountObjectsRequest {
+                deletion_blockers_only: Some(true),
+                ..AccountObjectsRequest::new(ClassicAddress::new(account)?)
+            })
+            .await?;
+        Ok(res
+            .account_objects
+            .map(|objects| objects.is_empty())
+            .unwrap_or(true))
+    }
+    /// Fetches an account's balance, reserve and spendable balance, requesting the account info
+    /// and server state concurrently.
+    pub async fn account_summary(&self, account: &str) -> Result<AccountSummary, Error> {
+        let req = AccountInfoRequest::new(ClassicAddress::new(account)?);
+        let (account_info, server_state) = futures::join!(
+            self.account_info(req),
+            self.server_state(ServerStateRequest::default())
+        );
+        let account_data = account_info?.account_data;
+        let ledger = server_state?
+            .state
+            .validated_ledger
+            .ok_or(ApiError::UnexpectedResponse(
+                "no validated ledger in server_state",
+            ))?;
+        let balance = match &account_data.balance {
+            CurrencyAmount::XRP(drops) => drops.clone(),
+            _ => return Err(ApiError::UnexpectedResponse("account balance is not XRP").into()),
+        };
+        let reserve = account_data.reserve(ledger.reserve_base, ledger.reserve_inc);
+        Ok(AccountSummary {
+            spendable: BigInt(balance.saturating_sub(*reserve)),
+            balance,
+            reserve,
+            owner_count: account_data.owner_count,
+            sequence: account_data.sequence,
+        })
+    }
+    /// Returns whether the amendment with the provided id or short name, such as
+    /// `fixNFTokenRemint`, is enabled. Servers return an error for amendments they don't know of.
+    pub async fn is_amendment_enabled(&self, amendment: &str) -> Result<bool, Error> {
+        let res = self
+            .feature(FeatureRequest {
+                feature: Some(amendment.to_owned()),
+            })
+            .await?;
+        Ok(res.get(amendment).map(|f| f.enabled).unwrap_or(false))
+    }
+    /// Returns the largest amount of XRP, in drops, the account can send while keeping its
+    /// reserve and paying the recommended fee. Returns 0 if the account is already at or below
+    /// its reserve.
+    pub async fn max_sendable_drops(&self, account: &str) -> Result<u64, Error> {
+        let (summary, fee) = futures::join!(self.account_summary(account), self.recommended_fee());
+        Ok(summary?.spendable.saturating_sub(*fee?))
+    }
+    /// Returns the trust line between `account` and `peer`, or `None` if the two accounts share
+    /// no trust line. Only the first page of the peer-filtered `account_lines` result is fetched.
+    pub async fn trust_line_between(
+        &self,
+        account: &str,
+        peer: &str,
+    ) -> Result<Option<AccountTrustLine>, Error> {
+        let res = self
+            .account_lines(AccountLinesRequest {
+                peer: Some(ClassicAddress::new(peer)?),
+                ..AccountLinesRequest::new(ClassicAddress::new(account)?)
+            })
+            .await?;
+        Ok(res
+            .lines
+            .unwrap_or_default()
+            .into_iter()
+            .find(|line| line.account == peer))
+    }
+    /// Determines whether a submitted transaction has a final outcome by searching the ledgers
+    /// from the one it was submitted in up to its LastLedgerSequence.
+    ///
+    /// A transaction is only reported as expired once the server confirms it searched every
+    /// ledger in that range, so `Outcome::Pending` is returned rather than a false expiry when
+    /// the server is missing ledgers. The range can span at most 1000 ledgers.
+    pub async fn final_outcome(
+        &self,
+        tx_hash: &str,
+        submitted_in_ledger: u32,
+        last_ledger_sequence: u32,
+    ) -> Result<Outcome, Error> {
+        let req = TxRequest {
+            min_ledger: Some(submitted_in_ledger),
+            max_ledger: Some(last_ledger_sequence),
+            ..TxRequest::new(tx_hash)?
+        };
+        match self.tx(req).await {
+            Ok(res) if res.validated == Some(true) => {
+                let result = res
+                    .meta
+                    .as_ref()
+                    .and_then(|meta| meta["TransactionResult"].as_str())
+                    .ok_or(ApiError::UnexpectedResponse(
+                        "no TransactionResult in validated transaction",
+                    ))?;
+                Ok(Outcome::Validated(EngineResult::from(result)))
+            }
+            Ok(_) => Ok(Outcome::Pending),
+            Err(Error::Api(ApiError::Response(e)))
+                if e.error == Some(ApiErrorCode::TxnNotFound) =>
+            {
+                if e.searched_all == Some(true) {
+                    Ok(Outcome::Expired)
+                } else {
+                    Ok(Outcome::Pending)
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+    /// Submits a signed transaction and waits for it to be validated, returning the validated
+    /// transaction. Check the `TransactionResult` in its metadata, as a validated transaction may
+    /// still have failed with a `tec` result.
+    ///
+    /// The transaction is looked up by hash each time a new ledger may have been validated, until
+    /// the latest validated ledger is past its LastLedgerSequence, at which point it can no
+    /// longer be included and `ApiError::TransactionExpired` is returned.
+    pub async fn submit_and_wait(&self, tx_blob: String) -> Result<TxResponse, Error> {
+        let tx_bytes =
+            hex::decode(&tx_blob).map_err(|e| serde_xrpl::error::Error::Message(e.to_string()))?;
+        let tx_hash = transaction::transaction_hash(&tx_bytes);
+        let last_ledger_sequence = serde_xrpl::de::from_bytes(&tx_bytes)?["LastLedgerSequence"]
+            .as_u64()
+            .ok_or(wallet::Error::LastLedgerSequenceRequired)?;
+        let submitted = self
+            .submit(SubmitRequest {
+                tx_blob,
+                ..Default::default()
+            })
+            .await?;
+        let engine_result = EngineResult::from(submitted.engine_result.as_str());
+        match engine_result.category() {
+            EngineResultCategory::Malformed | EngineResultCategory::Failure => {
+                return Err(ApiError::TransactionRejected(engine_result).into())
+            }
+            _ => {}
+        }
+        loop {
+            // The validated ledger is fetched before the transaction, so that a transaction
+            // validated in the last ledger it could be included in is always found.
+            let validated_ledger = self
+                .ledger(LedgerRequest {
+                    ledger_index: LedgerRequestIndex::Validated,
+                    ..Default::default()
+                })
+                .await?
+                .ledger
+                .ledger_info
+                .ledger_index
+                .ok_or(ApiError::UnexpectedResponse("no ledger_index in ledger"))?
+                .0;
+            match self.tx(TxRequest::new(&tx_hash)?).await {
+                Ok(res) if res.validated == Some(true) => return Ok(res),
+                Ok(_) => {}
+                Err(Error::Api(ApiError::Response(e)))
+                    if e.error == Some(ApiErrorCode::TxnNotFound) => {}
+                Err(e) => return Err(e),
+            }
+            if validated_ledger as u64 > last_ledger_sequence {
+                return Err(ApiError::TransactionExpired.into());
+            }
+            tokio::time::sleep(OUTCOME_POLL_INTERVAL).await;
+        }
+    }
+    /// Fills, signs and submits an NFTokenMint transaction, waits for it to be validated and
+    /// returns the NFTokenID of the minted token.
+    ///
+    /// Returns `ApiError::TransactionRejected` or `ApiError::TransactionExpired` as
+    /// `submit_and_wait` does, or `ApiError::TransactionFailed` if the transaction is validated
+    /// with a result other than tesSUCCESS.
+    pub async fn mint_and_get_id(
+        &self,
+        wallet: &mut Wallet,
+        mut mint_tx: Transaction,
+    ) -> Result<Hash256, Error> {
+        let tx_blob = wallet.fill_and_sign(&mut mint_tx, self).await?;
+        let res = self.submit_and_wait(tx_blob).await?;
+        let meta = res.meta.as_ref().ok_or(ApiError::UnexpectedResponse(
+            "no metadata in validated transaction",
+        ))?;
+        let result = meta["TransactionResult"]
+            .as_str()
+            .map(EngineResult::from)
+            .ok_or(ApiError::UnexpectedResponse(
+                "no TransactionResult in validated transaction",
+            ))?;
+        if result != EngineResult::TesSuccess {
+            return Err(ApiError::TransactionFailed(result).into());
+        }
+        minted_nftoken_id(meta)
+            .ok_or_else(|| ApiError::UnexpectedResponse("no NFTokenID in metadata").into())
+    }
 }
 
 impl<T: DuplexTransport> XRPL<T> {
     pub async fn subscribe(
         &self,
         request: SubscribeRequest,
-    ) -> Result<Pin<Box<dyn Stream<Item = Result<SubscriptionEvent, TransportError>>>>, TransportError> {
-        self.transport.subscribe(request).await
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<SubscriptionEvent, Error>>>>, Error> {
+        let events = self.transport.subscribe(request).await?;
+        Ok(Box::pin(events.map(|event| event.map_err(Error::from))))
+    }
+    /// Stops the subscription opened with the same request, ending its stream.
+    pub async fn unsubscribe(&self, request: SubscribeRequest) -> Result<(), Error> {
+        Ok(self.transport.unsubscribe(request).await?)
+    }
+    /// Opens a pathfinding request, yielding the paths found and then updated paths each time a
+    /// ledger closes, so that payment quotes stay current. Only one pathfinding request can be
+    /// open per connection; creating another closes the previous one. Call
+    /// [`XRPL::path_find_close`] to stop receiving updates.
+    pub async fn path_find(
+        &self,
+        request: PathFindRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<PathFindResponse, Error>>>>, Error> {
+        let paths = self.transport.path_find(request).await?;
+        Ok(Box::pin(paths.map(|paths| paths.map_err(Error::from))))
+    }
+    /// Closes the currently-open pathfinding request, ending its stream and returning the final
+    /// paths found.
+    pub async fn path_find_close(&self) -> Result<PathFindResponse, Error> {
+        Ok(self.transport.path_find_close().await?)
+    }
+    /// Returns the paths found by the currently-open pathfinding request.
+    pub async fn path_find_status(&self) -> Result<PathFindResponse, Error> {
+        Ok(self
+            .transport
+            .send_request("path_find", PathFindRequest::status())
+            .await?)
+    }
+    /// Follows the ledger stream, yielding each closed ledger with its transactions expanded.
+    ///
+    /// If `start` is provided, the ledgers from `start` up to the first closed ledger are fetched
+    /// first, so that no ledger is skipped between a previous sync and the subscription. Ledgers
+    /// missed between closes are also fetched, so every ledger is yielded in order. If fetching a
+    /// ledger fails, the error is yielded and the same ledger is fetched again on the next poll.
+    pub async fn follow_ledgers(
+        &self,
+        start: Option<u32>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<LedgerResponse, Error>> + '_>>, Error> {
+        let events = self
+            .subscribe(SubscribeRequest::streams(vec!["ledger".to_owned()]))
+            .await?;
+        Ok(Box::pin(futures::stream::unfold(
+            (events, start, 0u32),
+            move |(mut events, mut next, mut target)| async move {
+                loop {
+                    if let Some(index) = next.filter(|index| *index <= target) {
+                        let ledger = self
+                            .ledger(LedgerRequest {
+                                ledger_index: LedgerRequestIndex::Index(index),
+                                transactions: Some(true),
+                                expand: Some(true),
+                                ..Default::default()
+                            })
+                            .await;
+                        let next = if ledger.is_ok() { index + 1 } else { index };
+                        return Some((ledger, (events, Some(next), target)));
+                    }
+                    match events.next().await? {
+                        Ok(SubscriptionEvent::LedgerClosed(closed)) => {
+                            target = closed.ledger_index;
+                            next = next.or(Some(closed.ledger_index));
+                        }
+                        Ok(_) => {}
+                        Err(e) => return Some((Err(e), (events, next, target))),
+                    }
+                }
+            },
+        )))
+    }
+    /// Subscribes with the provided request and yields only transactions from validated ledgers,
+    /// along with their metadata. Proposed transactions, other events and errors are skipped, and
+    /// a transaction seen more than once (for example through both an account and the
+    /// transactions stream) is only yielded the first time.
+    pub async fn subscribe_validated_transactions(
+        &self,
+        request: SubscribeRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = (Transaction, TransactionMetadata)>>>, Error> {
+        let events = self.subscribe(request).await?;
+        let mut seen = HashSet::new();
+        let mut seen_order = VecDeque::new();
+        Ok(Box::pin(events.filter_map(move |event| {
+            let item = match event {
+                Ok(SubscriptionEvent::Transaction(event)) if event.validated == Some(true) => {
+                    match (event.transaction.hash.clone(), event.meta) {
+                        (Some(hash), Some(meta)) => {
+                            if seen.insert(hash.clone()) {
+                                seen_order.push_back(hash);
+                                // Only remember recent hashes so the set does not grow unbounded.
+                                if seen_order.len() > MAX_SEEN_TRANSACTIONS {
+                                    if let Some(oldest) = seen_order.pop_front() {
+                                        seen.remove(&oldest);
+                                    }
+                                }
+                                Some((event.transaction, meta))
+                            } else {
+                                None
+                            }
+                        }
+                        (None, Some(meta)) => Some((event.transaction, meta)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            futures::future::ready(item)
+        })))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::types::{BigInt, CurrencyAmount};
+    use crate::transaction::types::Payment;
+    use crate::types::{
+        path::{PathFindRequest, PathFindResponse},
+        submit::EngineResult,
+        subscribe::{SubscribeRequest, SubscriptionEvent},
+        tx::Outcome,
+        BigInt, CurrencyAmount, ErrorResponse,
+    };
+    use crate::wallet::Wallet;
+    use crate::{ApiError, Error};
+    use async_trait::async_trait;
+    use futures::stream::{Stream, StreamExt};
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::{json, Value};
+    use std::fmt::Debug;
+    use std::pin::Pin;
+    use std::sync::Mutex;
+
+    use super::{
+        transports::{DuplexTransport, HTTPBuilder, Transport, TransportError, WebSocket},
+        types, XRPL,
+    };
+
+    type Respond = dyn Fn(&str, &Value) -> Result<Value, Value> + Send + Sync;
+
+    /// A transport that answers each request with the result returned by `respond` for its
+    /// method and parameters, or with the error response returned instead. Subscribing yields
+    /// `events` and then ends the stream.
+    struct MockTransport {
+        respond: Box<Respond>,
+        requests: Mutex<Vec<(String, Value)>>,
+        events: Vec<Value>,
+    }
+
+    impl MockTransport {
+        fn new(respond: impl Fn(&str, &Value) -> Result<Value, Value> + Send + Sync + 'static) -> Self {
+            Self {
+                respond: Box::new(respond),
+                requests: Mutex::new(Vec::new()),
+                events: Vec::new(),
+            }
+        }
+
+        fn with_events(mut self, events: Vec<Value>) -> Self {
+            self.events = events;
+            self
+        }
+    }
+
+    #[async_trait]
+    impl Transport for MockTransport {
+        async fn send_request<Params: Serialize + Send, Res: DeserializeOwned + Debug + Send>(
+            &self,
+            method: &str,
+            params: Params,
+        ) -> Result<Res, TransportError> {
+            let params = serde_json::to_value(params).map_err(TransportError::JSONError)?;
+            let res = (self.respond)(method, &params);
+            self.requests
+                .lock()
+                .unwrap()
+                .push((method.to_owned(), params));
+            match res {
+                Ok(result) => serde_json::from_value(result).map_err(TransportError::JSONError),
+                Err(error) => Err(TransportError::APIError(
+                    serde_json::from_value::<ErrorResponse>(error).unwrap(),
+                )),
+            }
+        }
+    }
 
-    use super::{transports::HTTPBuilder, types, XRPL};
     #[test]
     fn create_client() {
-        let _ = XRPL::new(
+        let c = XRPL::new(
             HTTPBuilder::default()
                 .with_endpoint("http://s1.ripple.com:51234/")
                 .unwrap()
                 .build()
                 .unwrap(),
         );
+        assert!(!c.can_subscribe());
     }
     #[tokio::test]
     async fn account_info() {
@@ -237,13 +850,9 @@ mod tests {
                 .unwrap(),
         );
         let res = c
-            .account_info(types::account::AccountInfoRequest {
-                account: "rG1QQv2nh2gr7RCZ1P8YYcBUKCCN633jCn".to_owned(),
-                strict: None,
-                queue: None,
-                ledger_info: types::LedgerInfo::default(),
-                signer_lists: None,
-            })
+            .account_info(types::account::AccountInfoRequest::new(
+                "rG1QQv2nh2gr7RCZ1P8YYcBUKCCN633jCn".parse().unwrap(),
+            ))
             .await;
         match res {
             Err(e) => {
@@ -254,4 +863,247 @@ mod tests {
             }
         }
     }
+
+    #[async_trait]
+    impl DuplexTransport for MockTransport {
+        async fn subscribe(
+            &self,
+            _request: SubscribeRequest,
+        ) -> Result<
+            Pin<Box<dyn Stream<Item = Result<SubscriptionEvent, TransportError>>>>,
+            TransportError,
+        > {
+            let events: Vec<SubscriptionEvent> =
+                serde_json::from_value(Value::Array(self.events.clone()))
+                    .map_err(TransportError::JSONError)?;
+            Ok(Box::pin(futures::stream::iter(events.into_iter().map(Ok))))
+        }
+
+        async fn unsubscribe(&self, _request: SubscribeRequest) -> Result<(), TransportError> {
+            unimplemented!()
+        }
+
+        async fn path_find(
+            &self,
+            _request: PathFindRequest,
+        ) -> Result<
+            Pin<Box<dyn Stream<Item = Result<PathFindResponse, TransportError>>>>,
+            TransportError,
+        > {
+            unimplemented!()
+        }
+
+        async fn path_find_close(&self) -> Result<PathFindResponse, TransportError> {
+            unimplemented!()
+        }
+    }
+
+    /// Signs a payment from the genesis account that can be included up to `last_ledger_sequence`.
+    fn signed_payment(last_ledger_sequence: u32) -> String {
+        let wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        tx.account = wallet.address();
+        tx.sequence = 1;
+        tx.fee = BigInt(10);
+        tx.last_ledger_sequence = last_ledger_sequence;
+        wallet.sign(&mut tx).unwrap()
+    }
+
+    #[tokio::test]
+    async fn submit_and_wait_polls_validated_ledger() {
+        let tx_blob = signed_payment(100);
+        // The transaction is validated in ledger 100, its LastLedgerSequence, which is validated
+        // on the second poll. The open ledger is already 101, so a client polling it instead of
+        // the validated ledger would wrongly consider the transaction expired.
+        let polls = Mutex::new(0);
+        let c = XRPL::new(MockTransport::new(move |method, params| match method {
+            "submit" => Ok(json!({ "engine_result": "tesSUCCESS" })),
+            "ledger" => {
+                let mut polls = polls.lock().unwrap();
+                *polls += 1;
+                let ledger_index = match params["ledger_index"].as_str() {
+                    Some("validated") => 98 + *polls,
+                    _ => 101,
+                };
+                Ok(json!({ "ledger": { "ledger_index": ledger_index } }))
+            }
+            "tx" if *polls.lock().unwrap() < 2 => Ok(json!({ "hash": "", "validated": false })),
+            "tx" => Ok(json!({
+                "hash": "",
+                "ledger_index": 100,
+                "meta": { "TransactionResult": "tesSUCCESS" },
+                "validated": true
+            })),
+            method => panic!("unexpected request: {}", method),
+        }));
+        let res = c.submit_and_wait(tx_blob).await.unwrap();
+        assert_eq!(res.ledger_index, Some(100));
+    }
+
+    #[tokio::test]
+    async fn submit_and_wait_expires() {
+        let tx_blob = signed_payment(100);
+        let c = XRPL::new(MockTransport::new(|method, _| match method {
+            "submit" => Ok(json!({ "engine_result": "tesSUCCESS" })),
+            "ledger" => Ok(json!({ "ledger": { "ledger_index": 101 } })),
+            "tx" => Err(json!({ "error": "txnNotFound" })),
+            method => panic!("unexpected request: {}", method),
+        }));
+        assert!(matches!(
+            c.submit_and_wait(tx_blob).await,
+            Err(Error::Api(ApiError::TransactionExpired))
+        ));
+        let requests = c.transport.requests.lock().unwrap();
+        assert_eq!(requests[1].1["ledger_index"], "validated");
+    }
+
+    const TX_HASH: &str = "E08D6E9754025BA2534A78707605E0601F03ACE063687A0CA1BDDACFCD1698C7";
+
+    #[tokio::test]
+    async fn final_outcome_validated() {
+        let c = XRPL::new(MockTransport::new(|_, params| {
+            assert_eq!(params["min_ledger"], 90);
+            assert_eq!(params["max_ledger"], 100);
+            Ok(json!({
+                "hash": TX_HASH,
+                "meta": { "TransactionResult": "tecUNFUNDED_PAYMENT" },
+                "validated": true
+            }))
+        }));
+        assert_eq!(
+            c.final_outcome(TX_HASH, 90, 100).await.unwrap(),
+            Outcome::Validated(EngineResult::TecUnfundedPayment)
+        );
+    }
+
+    #[tokio::test]
+    async fn final_outcome_not_validated() {
+        let c = XRPL::new(MockTransport::new(|_, _| {
+            Ok(json!({
+                "hash": TX_HASH,
+                "meta": { "TransactionResult": "tesSUCCESS" },
+                "validated": false
+            }))
+        }));
+        assert_eq!(
+            c.final_outcome(TX_HASH, 90, 100).await.unwrap(),
+            Outcome::Pending
+        );
+    }
+
+    #[tokio::test]
+    async fn final_outcome_expired() {
+        let c = XRPL::new(MockTransport::new(|_, _| {
+            Err(json!({ "error": "txnNotFound", "searched_all": true }))
+        }));
+        assert_eq!(
+            c.final_outcome(TX_HASH, 90, 100).await.unwrap(),
+            Outcome::Expired
+        );
+    }
+
+    #[tokio::test]
+    async fn final_outcome_missing_ledgers() {
+        // The server is missing some of the ledgers, so the transaction may still be in one.
+        let c = XRPL::new(MockTransport::new(|_, _| {
+            Err(json!({ "error": "txnNotFound", "searched_all": false }))
+        }));
+        assert_eq!(
+            c.final_outcome(TX_HASH, 90, 100).await.unwrap(),
+            Outcome::Pending
+        );
+    }
+
+    #[tokio::test]
+    async fn follow_ledgers_backfills_and_retries() {
+        // Ledgers 3 and 4 are backfilled before the first close, ledgers 6 and 7 are fetched for
+        // the gap between the two closes, and the failed fetch of ledger 4 is retried.
+        let failed = Mutex::new(false);
+        let c = XRPL::new(
+            MockTransport::new(move |method, params| {
+                assert_eq!(method, "ledger");
+                assert_eq!(params["transactions"], true);
+                assert_eq!(params["expand"], true);
+                let ledger_index = params["ledger_index"].as_u64().unwrap();
+                let mut failed = failed.lock().unwrap();
+                if ledger_index == 4 && !*failed {
+                    *failed = true;
+                    return Err(json!({ "error": "lgrNotFound" }));
+                }
+                Ok(json!({ "ledger": { "ledger_index": ledger_index } }))
+            })
+            .with_events(vec![
+                json!({ "type": "ledgerClosed", "ledger_hash": "", "ledger_index": 5 }),
+                json!({ "type": "ledgerClosed", "ledger_hash": "", "ledger_index": 8 }),
+            ]),
+        );
+        let ledgers = c
+            .follow_ledgers(Some(3))
+            .await
+            .unwrap()
+            .map(|ledger| {
+                ledger
+                    .ok()
+                    .and_then(|ledger| ledger.ledger.ledger_info.ledger_index)
+                    .map(|index| index.0)
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            ledgers,
+            vec![Some(3), None, Some(4), Some(5), Some(6), Some(7), Some(8)]
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_websocket_requests() {
+        let c = XRPL::new(
+            WebSocket::builder()
+                .with_endpoint("wss://xrplcluster.com/")
+                .unwrap()
+                .build()
+                .await
+                .unwrap(),
+        );
+        let request = |ledger_index| types::account::AccountInfoRequest {
+            ledger_info: types::LedgerInfo {
+                ledger_index,
+                ..Default::default()
+            },
+            ..types::account::AccountInfoRequest::new(
+                "rG1QQv2nh2gr7RCZ1P8YYcBUKCCN633jCn".parse().unwrap(),
+            )
+        };
+        let latest = match c.account_info(request(None)).await {
+            Err(e) => {
+                eprintln!("test failed: {:?}", e);
+                return;
+            }
+            Ok(res) => res.ledger_info.ledger_current_index.unwrap() as u32 - 1,
+        };
+        // Query a different ledger with each request so that a response delivered to the wrong
+        // request is detected.
+        let ledgers: Vec<u32> = (0..100).map(|i| latest - i).collect();
+        let responses = futures::future::join_all(
+            ledgers
+                .iter()
+                .map(|i| c.account_info(request(Some(types::Integer(*i))))),
+        )
+        .await;
+        for (ledger, res) in ledgers.iter().zip(responses) {
+            match res {
+                Err(e) => {
+                    eprintln!("test failed: {:?}", e);
+                }
+                Ok(res) => {
+                    assert_eq!(res.ledger_info.ledger_index, Some(types::Integer(*ledger)));
+                }
+            }
+        }
+    }
 }
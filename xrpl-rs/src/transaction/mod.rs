@@ -1 +1,194 @@
-pub mod types;
\ No newline at end of file
+pub mod types;
+
+use serde_xrpl::error::Error;
+use sha2::{Digest, Sha512};
+
+use crate::types::BigInt;
+use types::{Signer, Transaction};
+
+/// A signature by one of the signers of a multisigning account, along with the transaction it
+/// signed.
+#[derive(Debug, Clone)]
+pub struct SignerProof {
+    /// The transaction that was signed, with an empty SigningPubKey and no signatures.
+    pub transaction: Transaction,
+    /// The signer's signature.
+    pub signer: Signer,
+}
+
+/// Returns the fee for a multisigned transaction, which costs the base fee once for the
+/// transaction and once more for each signature.
+pub fn multisign_fee(base_fee: BigInt, num_signers: usize) -> BigInt {
+    BigInt(base_fee.0.saturating_mul(1 + num_signers as u64))
+}
+
+/// Prepares a transaction to be signed by `num_signers` signers with `Wallet::multi_sign`,
+/// setting its Fee to `multisign_fee(base_fee, num_signers)` and clearing its SigningPubKey and
+/// any signatures.
+///
+/// Every signer signs the Fee, so it must be set before the first signature rather than once the
+/// signatures are combined.
+pub fn prepare_multisign(tx: &mut Transaction, base_fee: BigInt, num_signers: usize) {
+    tx.fee = multisign_fee(base_fee, num_signers);
+    tx.signing_pub_key = String::new();
+    tx.txn_signature = None;
+    tx.signers = None;
+}
+
+/// Returns the hash identifying a serialized transaction, the first half of the SHA-512 of the
+/// blob prefixed with `TXN\0`, as upper case hex.
+pub fn transaction_hash(tx_blob: &[u8]) -> String {
+    let mut h = Sha512::new();
+    h.update(serde_xrpl::hash_prefixes::TRANSACTION_ID);
+    h.update(tx_blob);
+    hex::encode_upper(&h.finalize()[..32])
+}
+
+/// Serializes an already signed transaction into the hex encoded blob accepted by `submit`,
+/// without signing it again.
+pub fn serialize_signed(tx: &Transaction) -> Result<String, Error> {
+    if tx.txn_signature.is_none() && tx.signers.is_none() {
+        return Err(Error::Message("transaction is not signed".to_owned()));
+    }
+    tx.check_extra()?;
+    let value = serde_json::to_value(tx).map_err(|e| Error::Message(e.to_string()))?;
+    Ok(hex::encode(serde_xrpl::ser::to_bytes(&value)?).to_uppercase())
+}
+
+/// Combines the signatures of a multisigned transaction into a transaction ready to be submitted.
+///
+/// Every proof must be for the same transaction. The signers are sorted as by `combine_signers`.
+pub fn combine_signatures(signers: Vec<SignerProof>) -> Result<Transaction, Error> {
+    let mut signers = signers.into_iter();
+    let first = signers
+        .next()
+        .ok_or_else(|| Error::Message("no signatures to combine".to_owned()))?;
+    let mut tx = first.transaction;
+    let mut combined = vec![first.signer];
+    for proof in signers {
+        if proof.transaction != tx {
+            return Err(Error::Message(
+                "signatures are for different transactions".to_owned(),
+            ));
+        }
+        combined.push(proof.signer);
+    }
+    combine_signers(&mut tx, combined)?;
+    Ok(tx)
+}
+
+/// Sets the Signers of a multisigned transaction, as returned by `Wallet::multi_sign`, clearing
+/// its SigningPubKey and any single signature.
+///
+/// The Fee is not adjusted, as every signer signs it. It must already cover each signature, as set
+/// by `prepare_multisign`, or the transaction is rejected with `telINSUF_FEE_P`.
+///
+/// rippled requires the Signers array to be sorted by the numeric value of each signer's account
+/// ID, which differs from the order of the addresses, so the signers are sorted by their decoded
+/// account IDs.
+pub fn combine_signers(tx: &mut Transaction, signers: Vec<Signer>) -> Result<(), Error> {
+    if signers.is_empty() {
+        return Err(Error::Message("no signatures to combine".to_owned()));
+    }
+    let mut sorted = signers
+        .into_iter()
+        .map(|signer| Ok((decode_account_id(&signer.account)?, signer)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+    tx.signing_pub_key = String::new();
+    tx.txn_signature = None;
+    tx.signers = Some(sorted.into_iter().map(|(_, signer)| signer).collect());
+    Ok(())
+}
+
+pub(crate) fn decode_account_id(address: &str) -> Result<Vec<u8>, Error> {
+    serde_xrpl::utils::decode_base58(address, &[0x00])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::types::{Payment, Signer};
+    use super::{
+        combine_signatures, multisign_fee, prepare_multisign, transaction_hash, SignerProof,
+    };
+    use crate::types::{BigInt, CurrencyAmount};
+
+    #[test]
+    fn transaction_hashes() {
+        let tx_blob = hex::decode("120000220000000024001ABED86140000000000F424068400000000000000C732103EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE38114DD76483FACDEE26E60D8A586BB58D09F27045C4683140A20B3C85F482532A9578DBB3950B85CA06594D1").unwrap();
+        assert_eq!(
+            transaction_hash(&tx_blob),
+            "01E083957C77ABCD886A7FD5522155ED0BE40A2CDDF5A9E2520873B2D91FF637"
+        );
+    }
+
+    #[test]
+    fn multisign_fees() {
+        assert_eq!(multisign_fee(BigInt(10), 1), BigInt(20));
+        assert_eq!(multisign_fee(BigInt(10), 3), BigInt(40));
+        assert_eq!(multisign_fee(BigInt(12), 8), BigInt(108));
+    }
+
+    #[test]
+    fn prepare_multisign_sets_fee() {
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        tx.fee = BigInt(10);
+        tx.signing_pub_key =
+            "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3".to_owned();
+        tx.txn_signature = Some("3044".to_owned());
+        prepare_multisign(&mut tx, BigInt(10), 3);
+        assert_eq!(tx.fee, BigInt(40));
+        assert_eq!(tx.signing_pub_key, "");
+        assert!(tx.txn_signature.is_none() && tx.signers.is_none());
+    }
+
+    fn proof(tx: &crate::transaction::types::Transaction, account: &str) -> SignerProof {
+        SignerProof {
+            transaction: tx.clone(),
+            signer: Signer {
+                account: account.to_owned(),
+                signing_pub_key:
+                    "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3".to_owned(),
+                txn_signature: "3044".to_owned(),
+            },
+        }
+    }
+
+    #[test]
+    fn combine_signatures_sorts_by_account_id() {
+        let tx = Payment {
+            amount: CurrencyAmount::xrp(1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        // Sorted by address, but their account IDs are 0xDD76..., 0x1014... and 0x0A20....
+        let accounts = [
+            "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+            "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw",
+            "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+        ];
+        let combined =
+            combine_signatures(accounts.iter().map(|account| proof(&tx, account)).collect())
+                .unwrap();
+        let signers: Vec<_> = combined
+            .signers
+            .unwrap()
+            .into_iter()
+            .map(|signer| signer.account)
+            .collect();
+        assert_eq!(signers, [accounts[2], accounts[1], accounts[0]]);
+
+        let mut other = tx.clone();
+        other.sequence = 1;
+        assert!(
+            combine_signatures(vec![proof(&tx, accounts[0]), proof(&other, accounts[1])]).is_err()
+        );
+        assert!(combine_signatures(vec![]).is_err());
+    }
+}
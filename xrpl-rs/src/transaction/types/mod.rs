@@ -1,6 +1,10 @@
-use crate::types::{Address, BigInt, CurrencyAmount, H256};
+use crate::types::{
+    path::PathSet, Address, BigInt, CurrencyAmount, Hash256, SignerEntry, XAddress, H256,
+};
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
 
 macro_rules! into_transaction {
     ($name: ident) => {
@@ -14,7 +18,7 @@ macro_rules! into_transaction {
     };
 }
 
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Default, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Transaction {
     pub account: Address,
@@ -24,9 +28,200 @@ pub struct Transaction {
     pub signing_pub_key: String,
     pub txn_signature: Option<String>,
     pub flags: Option<TFFlag>,
+    /// (Optional) Additional arbitrary information used to identify this transaction.
+    #[serde(rename = "Memos", default, with = "wrapped::option")]
+    pub memos: Option<Vec<Memo>>,
+    /// (Optional) Array of signatures authorizing a multisigned transaction, sorted by account.
+    #[serde(rename = "Signers", default, with = "wrapped::option")]
+    pub signers: Option<Vec<Signer>>,
     #[serde(flatten)]
     pub tx: Option<TransactionType>,
+    #[serde(rename = "hash")]
     pub hash: Option<String>,
+    /// Additional fields, by their name in `definitions.json`, for fields this crate does not
+    /// model yet. The serializer looks up each field's type by name, so values must use the JSON
+    /// representation rippled expects for that type. Names unknown to the bundled definitions,
+    /// or also set through a typed field, are rejected by `check_extra` when signing or
+    /// converting to JSON. Not populated when deserializing.
+    #[serde(flatten, skip_deserializing)]
+    pub extra: Option<HashMap<String, Value>>,
+}
+
+impl Transaction {
+    /// Returns the transaction as the JSON expected by rippled's `sign` and `submit` methods, with
+    /// PascalCase transaction fields, lowercase `currency`, `issuer` and `value` fields inside
+    /// amounts, and unset optional fields omitted.
+    pub fn to_rippled_json(&self) -> Result<Value, serde_json::Error> {
+        self.check_extra().map_err(serde::ser::Error::custom)?;
+        let mut value = serde_json::to_value(self)?;
+        remove_nulls(&mut value);
+        Ok(value)
+    }
+    /// Checks that every extra field is a serialized field of the bundled definitions and is not
+    /// also set through a typed field, so that the JSON and the signed blob hold the same fields.
+    pub fn check_extra(&self) -> Result<(), serde_xrpl::error::Error> {
+        let extra = match &self.extra {
+            Some(extra) => extra,
+            None => return Ok(()),
+        };
+        let typed = serde_json::to_value(Transaction {
+            extra: None,
+            ..self.clone()
+        })
+        .map_err(|e| serde_xrpl::error::Error::Message(e.to_string()))?;
+        for name in extra.keys() {
+            if serde_xrpl::definitions::is_serialized_field(name) != Some(true) {
+                return Err(serde_xrpl::error::Error::UnknownFieldName(name.clone()));
+            }
+            if !typed[name].is_null() {
+                return Err(serde_xrpl::error::Error::Message(format!(
+                    "extra field {} is also set through a typed field",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+    /// Sets the public key and signature produced by an external signer, such as an HSM, so the
+    /// transaction can be serialized with `transaction::serialize_signed`.
+    pub fn with_external_signature(mut self, pub_key_hex: &str, signature_hex: &str) -> Self {
+        self.signing_pub_key = pub_key_hex.to_uppercase();
+        self.txn_signature = Some(signature_hex.to_uppercase());
+        self
+    }
+}
+
+/// Arbitrary data attached to a transaction, such as an invoice id or a destination tag for
+/// exchanges. All fields are hex encoded and their total size is limited to 1 KB.
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Memo {
+    /// (Optional) Arbitrary hex value, conventionally containing the content of the memo.
+    pub memo_data: Option<String>,
+    /// (Optional) Hex value representing characters allowed in URLs. Conventionally containing information on how the memo is encoded, for example as a MIME type.
+    pub memo_format: Option<String>,
+    /// (Optional) Hex value representing characters allowed in URLs. Conventionally, a unique relation (according to RFC 5988) that defines the format of this memo.
+    pub memo_type: Option<String>,
+}
+
+/// A signature by one of the signers of a multisigning account.
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Signer {
+    /// The address associated with this signature, as it appears in the signer list.
+    pub account: Address,
+    /// The public key used to verify the validity of this signature.
+    pub signing_pub_key: String,
+    /// A signature over all signing fields of the transaction, and the signer's account ID.
+    pub txn_signature: String,
+}
+
+/// Serializes arrays of inner objects, such as Memos, whose elements are each wrapped in an
+/// object with the element's field name as its only key, e.g. `[{"Memo": {...}}]`.
+mod wrapped {
+    use super::{
+        Deserialize, Deserializer, Memo, Serialize, Serializer, Signer, SignerEntry, Transaction,
+    };
+    use serde::{de, ser::SerializeMap};
+    use std::collections::BTreeMap;
+
+    /// An inner object that is wrapped in an object with its field name when in an array.
+    pub trait Wrapped {
+        const FIELD_NAME: &'static str;
+    }
+
+    impl Wrapped for Memo {
+        const FIELD_NAME: &'static str = "Memo";
+    }
+
+    impl Wrapped for Signer {
+        const FIELD_NAME: &'static str = "Signer";
+    }
+
+    impl Wrapped for SignerEntry {
+        const FIELD_NAME: &'static str = "SignerEntry";
+    }
+
+    impl Wrapped for Transaction {
+        const FIELD_NAME: &'static str = "RawTransaction";
+    }
+
+    struct WrappedRef<'a, T>(&'a T);
+
+    impl<T: Wrapped + Serialize> Serialize for WrappedRef<'_, T> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry(T::FIELD_NAME, self.0)?;
+            map.end()
+        }
+    }
+
+    fn unwrap<T: Wrapped, E: de::Error>(mut wrapper: BTreeMap<String, T>) -> Result<T, E> {
+        wrapper
+            .remove(T::FIELD_NAME)
+            .ok_or_else(|| E::missing_field(T::FIELD_NAME))
+    }
+
+    pub fn serialize<T, S>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Wrapped + Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(values.iter().map(WrappedRef))
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        T: Wrapped + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Vec::<BTreeMap<String, T>>::deserialize(deserializer)?
+            .into_iter()
+            .map(unwrap)
+            .collect()
+    }
+
+    /// The same for optional arrays, which are omitted when unset.
+    pub mod option {
+        use super::{Deserialize, Deserializer, Serializer, Wrapped};
+        use serde::Serialize;
+        use std::collections::BTreeMap;
+
+        pub fn serialize<T, S>(values: &Option<Vec<T>>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: Wrapped + Serialize,
+            S: Serializer,
+        {
+            match values {
+                Some(values) => super::serialize(values, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+        where
+            T: Wrapped + Deserialize<'de>,
+            D: Deserializer<'de>,
+        {
+            Option::<Vec<BTreeMap<String, T>>>::deserialize(deserializer)?
+                .map(|values| values.into_iter().map(super::unwrap).collect())
+                .transpose()
+        }
+    }
+}
+
+fn remove_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(remove_nulls);
+        }
+        Value::Array(values) => values.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
 }
 
 type TFFlag = u32;
@@ -44,12 +239,18 @@ pub const TF_ONLY_XRP: TFFlag = 0x00000002;
 pub const TF_TRUSTLINE: TFFlag = 0x00000004;
 pub const TF_TRANSFERABLE: TFFlag = 0x00000008;
 
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub const TF_NO_RIPPLE_DIRECT: TFFlag = 0x00010000;
+pub const TF_PARTIAL_PAYMENT: TFFlag = 0x00020000;
+pub const TF_LIMIT_QUALITY: TFFlag = 0x00040000;
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(tag = "TransactionType", rename_all = "PascalCase")]
 pub enum TransactionType {
     Payment(Payment),
     AccountSet(AccountSet),
     AccountDelete(AccountDelete),
+    SetRegularKey(SetRegularKey),
+    SignerListSet(SignerListSet),
     CheckCancel(CheckCancel),
     CheckCash(CheckCash),
     CheckCreate(CheckCreate),
@@ -58,27 +259,122 @@ pub enum TransactionType {
     PaymentChannelCreate(PaymentChannelCreate),
     PaymentChannelFund(PaymentChannelFund),
     NFTokenMint(NFTokenMint),
+    Batch(Batch),
+    #[serde(rename = "MPTokenIssuanceCreate")]
+    MPTokenIssuanceCreate(MPTokenIssuanceCreate),
+    #[serde(rename = "MPTokenAuthorize")]
+    MPTokenAuthorize(MPTokenAuthorize),
+    Clawback(Clawback),
+    OfferCreate(OfferCreate),
+    OfferCancel(OfferCancel),
+    EscrowCreate(EscrowCreate),
+    EscrowFinish(EscrowFinish),
+    EscrowCancel(EscrowCancel),
 }
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct EscrowCreate {}
-
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Payment {
     /// The amount of currency to deliver. For non-XRP amounts, the nested field names MUST be lower-case. If the tfPartialPayment flag is set, deliver up to this amount instead.
     pub amount: CurrencyAmount,
     /// The unique address of the account receiving the payment.
     pub destination: Address,
+    /// (Optional) Arbitrary tag that identifies the reason for the payment to the destination, or a hosted recipient to pay.
+    pub destination_tag: Option<u32>,
+    /// (Optional) Highest amount of source currency this transaction is allowed to cost, including transfer fees, exchange rates, and slippage. Does not include the XRP destroyed as a cost for submitting the transaction. Must be supplied for cross-currency/cross-issue payments. Must be omitted for XRP-to-XRP payments.
+    pub send_max: Option<CurrencyAmount>,
+    /// (Optional) Minimum amount of destination currency this transaction should deliver. Only valid if this is a partial payment. For non-XRP amounts, the nested field names are lower-case.
+    pub deliver_min: Option<CurrencyAmount>,
+    /// (Optional) Arbitrary 256-bit hash representing a specific reason or identifier for this payment.
+    #[serde(rename = "InvoiceID")]
+    pub invoice_id: Option<H256>,
+    /// (Optional) Arbitrary integer used to identify the reason for this payment, or a sender on whose behalf this transaction is made.
+    pub source_tag: Option<u32>,
+    /// (Optional, auto-fillable) Array of payment paths to be used for this transaction. Must be omitted for XRP-to-XRP transactions.
+    pub paths: Option<PathSet>,
+}
+
+impl Payment {
+    /// Checks that the Amount, SendMax and DeliverMin fields are consistent with each other and
+    /// with the transaction flags, which the network would otherwise reject as malformed with
+    /// `temBAD_AMOUNT` or `temMALFORMED`. Payments with Paths are cross-currency, so they must
+    /// state the source amount to spend in SendMax.
+    pub fn validate(&self, flags: Option<TFFlag>) -> Result<(), crate::Error> {
+        let invalid = |reason: &str| -> crate::Error {
+            crate::ValidationError::InvalidAmount(reason.to_owned()).into()
+        };
+        let partial = flags.unwrap_or(0) & TF_PARTIAL_PAYMENT != 0;
+        let xrp_amount = matches!(self.amount, CurrencyAmount::XRP(_));
+        if self.paths.is_some() && self.send_max.is_none() {
+            return Err(invalid("a cross-currency payment requires SendMax"));
+        }
+        match &self.send_max {
+            Some(CurrencyAmount::XRP(_)) if xrp_amount => {
+                return Err(invalid("SendMax cannot be XRP for an XRP payment"));
+            }
+            None if partial && xrp_amount => {
+                return Err(invalid("an XRP-to-XRP payment cannot be partial"));
+            }
+            _ => {}
+        }
+        if let Some(deliver_min) = &self.deliver_min {
+            if !partial {
+                return Err(invalid("DeliverMin requires tfPartialPayment"));
+            }
+            let within_amount = match (deliver_min, &self.amount) {
+                (CurrencyAmount::XRP(min), CurrencyAmount::XRP(amount)) => min.0 <= amount.0,
+                (CurrencyAmount::IssuedCurrency(min), CurrencyAmount::IssuedCurrency(amount)) => {
+                    min.currency == amount.currency
+                        && min.issuer == amount.issuer
+                        && min.value <= amount.value
+                }
+                (CurrencyAmount::MPT(min), CurrencyAmount::MPT(amount)) => {
+                    min.mpt_issuance_id == amount.mpt_issuance_id
+                        && matches!(
+                            (min.value.parse::<u64>(), amount.value.parse::<u64>()),
+                            (Ok(min), Ok(amount)) if min <= amount
+                        )
+                }
+                _ => false,
+            };
+            if deliver_min.is_zero() || !within_amount {
+                return Err(invalid(
+                    "DeliverMin must be a positive amount of the Amount's currency, up to Amount",
+                ));
+            }
+        }
+        Ok(())
+    }
+    /// Creates a payment to an X-address, setting the destination to its classic address and the
+    /// destination tag to its tag, if any. `test_network` is whether the payment is sent on a test
+    /// network, such as testnet or devnet, and X-addresses for the other kind of network are
+    /// rejected.
+    pub fn to_x_address(
+        x_address: &str,
+        amount: CurrencyAmount,
+        test_network: bool,
+    ) -> Result<Payment, crate::Error> {
+        let x_address = XAddress::new(x_address)?;
+        if x_address.test_network != test_network {
+            let address = x_address.classic_address.into();
+            return Err(crate::ValidationError::WrongNetwork(address).into());
+        }
+        Ok(Payment {
+            amount,
+            destination: x_address.classic_address.into(),
+            destination_tag: x_address.tag,
+            ..Default::default()
+        })
+    }
 }
 
 into_transaction!(Payment);
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct AccountSet {
     /// (Optional) Unique identifier of a flag to disable for this account.
-    pub clear_flag: Option<u32>,
+    pub clear_flag: Option<AccountSetFlag>,
     /// (Optional) The domain that owns this account, as a string of hex representing the ASCII for the domain in lowercase. Cannot be more than 256 bytes in length.
     pub domain: Option<String>,
     /// (Optional) Hash of an email address to be used for generating an avatar image. Conventionally, clients use Gravatar  to display this image.
@@ -91,6 +387,22 @@ pub struct AccountSet {
     pub transfer_rate: Option<u32>,
     /// (Optional) Tick size to use for offers involving a currency issued by this address. The exchange rates of those offers is rounded to this many significant digits. Valid values are 3 to 15 inclusive, or 0 to disable. (Added by the TickSize amendment.)
     pub tick_size: Option<u8>,
+    /// (Optional) Another account that can mint NFTokens for you. To set it, also enable ASF_AUTHORIZED_NFTOKEN_MINTER. To remove it, disable ASF_AUTHORIZED_NFTOKEN_MINTER and omit this field. (Added by the NonFungibleTokensV1_1 amendment.)
+    #[serde(rename = "NFTokenMinter")]
+    pub nftoken_minter: Option<Address>,
+}
+
+impl AccountSet {
+    /// Enables the provided ASF_* flag for this account.
+    pub fn enable(mut self, flag: AccountSetFlag) -> Self {
+        self.set_flag = Some(flag);
+        self
+    }
+    /// Disables the provided ASF_* flag for this account.
+    pub fn disable(mut self, flag: AccountSetFlag) -> Self {
+        self.clear_flag = Some(flag);
+        self
+    }
 }
 
 type AccountSetFlag = u32;
@@ -104,10 +416,16 @@ pub const ASF_GLOBAL_FREEZE: AccountSetFlag = 7;
 pub const ASF_NO_FREEZE: AccountSetFlag = 6;
 pub const ASF_REQUIRE_AUTH: AccountSetFlag = 2;
 pub const ASF_REQUIRE_DEST: AccountSetFlag = 1;
+pub const ASF_AUTHORIZED_NFTOKEN_MINTER: AccountSetFlag = 10;
+pub const ASF_DISALLOW_INCOMING_NFTOKEN_OFFER: AccountSetFlag = 12;
+pub const ASF_DISALLOW_INCOMING_CHECK: AccountSetFlag = 13;
+pub const ASF_DISALLOW_INCOMING_PAY_CHAN: AccountSetFlag = 14;
+pub const ASF_DISALLOW_INCOMING_TRUSTLINE: AccountSetFlag = 15;
+pub const ASF_ALLOW_TRUSTLINE_CLAWBACK: AccountSetFlag = 16;
 
 into_transaction!(AccountSet);
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct AccountDelete {
     /// The address of an account to receive any leftover XRP after deleting the sending account. Must be a funded account in the ledger, and must not be the sending account.
@@ -118,29 +436,87 @@ pub struct AccountDelete {
 
 into_transaction!(AccountDelete);
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SetRegularKey {
+    /// (Optional) A base-58-encoded Address that indicates the regular key pair to be assigned to the account. If omitted, removes any existing regular key pair from the account. Must not match the master key pair for the address.
+    pub regular_key: Option<Address>,
+}
+
+into_transaction!(SetRegularKey);
+
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SignerListSet {
+    /// A target number for the signer weights. A multi-signature from this list is valid only if the sum weights of the signatures provided is greater than or equal to this value. To delete a signer list, use the value 0.
+    pub signer_quorum: u32,
+    /// (Omitted when deleting) Array of SignerEntry objects, indicating the addresses and weights of signers in this list. This signer list must have at least 1 member and no more than 32 members. No address may appear more than once in the list, nor may the Account submitting the transaction appear in the list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "wrapped")]
+    pub signer_entries: Vec<SignerEntry>,
+}
+
+impl SignerListSet {
+    /// Creates a transaction deleting the account's signer list, which has a quorum of 0 and no
+    /// signer entries.
+    pub fn delete() -> Self {
+        Self::default()
+    }
+}
+
+into_transaction!(SignerListSet);
+
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct CheckCancel {
     /// The ID of the Check ledger object to cancel, as a 64-character hexadecimal string.
+    #[serde(rename = "CheckID")]
     pub check_id: H256,
 }
 
 into_transaction!(CheckCancel);
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct CheckCash {
     /// The ID of the Check ledger object to cash, as a 64-character hexadecimal string.
+    #[serde(rename = "CheckID")]
     pub check_id: H256,
-    /// (Optional) Redeem the Check for exactly this amount, if possible. The currency must match that of the SendMax of the corresponding CheckCreate transaction. You must provide either this field or DeliverMin.
-    pub amount: Option<CurrencyAmount>,
-    /// (Optional) Redeem the Check for at least this amount and for as much as possible. The currency must match that of the SendMax of the corresponding CheckCreate transaction. You must provide either this field or Amount.
-    pub deliver_min: Option<CurrencyAmount>,
+    /// How much to redeem the Check for. Exactly one of Amount and DeliverMin is provided.
+    #[serde(flatten)]
+    pub amount: CheckCashAmount,
+}
+
+impl CheckCash {
+    /// Creates a transaction redeeming the Check for exactly the provided amount.
+    pub fn exact(check_id: &str, amount: CurrencyAmount) -> Self {
+        Self {
+            check_id: check_id.to_owned(),
+            amount: CheckCashAmount::Amount(amount),
+        }
+    }
+    /// Creates a transaction redeeming the Check for as much as possible, failing if that is
+    /// less than the provided amount.
+    pub fn at_least(check_id: &str, deliver_min: CurrencyAmount) -> Self {
+        Self {
+            check_id: check_id.to_owned(),
+            amount: CheckCashAmount::DeliverMin(deliver_min),
+        }
+    }
+}
+
+/// The amount a CheckCash transaction redeems a Check for. The currency must match that of the
+/// SendMax of the corresponding CheckCreate transaction.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub enum CheckCashAmount {
+    /// Redeem the Check for exactly this amount, if possible.
+    Amount(CurrencyAmount),
+    /// Redeem the Check for at least this amount and for as much as possible.
+    DeliverMin(CurrencyAmount),
 }
 
 into_transaction!(CheckCash);
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct CheckCreate {
     /// The unique address of the account that can cash the Check.
@@ -158,7 +534,7 @@ pub struct CheckCreate {
 
 into_transaction!(CheckCreate);
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct TrustSet {
     /// Object defining the trust line to create or modify, in the format of a Currency Amount.
@@ -169,7 +545,7 @@ pub struct TrustSet {
     pub quality_out: Option<u32>,
 }
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct TrustSetLimitAmount {
     /// The currency to this trust line applies to, as a three-letter ISO 4217 Currency Code  or a 160-bit hex value according to currency format. "XRP" is invalid.
     pub currency: String,
@@ -181,11 +557,11 @@ pub struct TrustSetLimitAmount {
 
 into_transaction!(TrustSet);
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct PaymentChannelClaim {
     /// The unique ID of the channel, as a 64-character hexadecimal string.
-    pub channel: H256,
+    pub channel: Hash256,
     /// (Optional) Total amount of XRP, in drops, delivered by this channel after processing this claim. Required to deliver XRP. Must be more than the total amount delivered by the channel so far, but not greater than the Amount of the signed claim. Must be provided except when closing the channel.
     pub balance: Option<BigInt>,
     /// (Optional) The amount of XRP, in drops, authorized by the Signature. This must match the amount in the signed message. This is the cumulative amount of XRP that can be dispensed by the channel, including XRP previously redeemed.
@@ -198,7 +574,7 @@ pub struct PaymentChannelClaim {
 
 into_transaction!(PaymentChannelClaim);
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct PaymentChannelCreate {
     /// Amount of XRP, in drops, to deduct from the sender's balance and set aside in this channel. While the channel is open, the XRP can only go to the Destination address. When the channel closes, any unclaimed XRP is returned to the source address's balance.
@@ -213,17 +589,100 @@ pub struct PaymentChannelCreate {
     pub cancel_after: Option<u32>,
     /// (Optional) Arbitrary tag to further specify the destination for this payment channel, such as a hosted recipient at the destination address.
     pub destination_tag: Option<u32>,
+    /// (Optional) Arbitrary tag to identify the reason for the payment channel, or a hosted sender to route payments from.
+    pub source_tag: Option<u32>,
 }
 
 into_transaction!(PaymentChannelCreate);
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
-pub struct PaymentChannelFund {}
+pub struct PaymentChannelFund {
+    /// The unique ID of the channel to fund, as a 64-character hexadecimal string.
+    pub channel: Hash256,
+    /// Amount of XRP, in drops to add to the channel. Must be a positive amount of XRP.
+    pub amount: BigInt,
+    /// (Optional) New Expiration time to set for the channel, in seconds since the Ripple Epoch. This must be later than either the current time plus the SettleDelay of the channel, or the existing Expiration of the channel. After the Expiration time, any transaction that would access the channel closes the channel without taking its normal action. Any unspent XRP is returned to the source address when the channel closes. (Expiration is separate from the channel's immutable CancelAfter time.)
+    pub expiration: Option<u32>,
+}
 
 into_transaction!(PaymentChannelFund);
 
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub const TF_PASSIVE: TFFlag = 0x00010000;
+pub const TF_IMMEDIATE_OR_CANCEL: TFFlag = 0x00020000;
+pub const TF_FILL_OR_KILL: TFFlag = 0x00040000;
+pub const TF_SELL: TFFlag = 0x00080000;
+
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct OfferCreate {
+    /// The amount and type of currency being sold.
+    pub taker_gets: CurrencyAmount,
+    /// The amount and type of currency being bought.
+    pub taker_pays: CurrencyAmount,
+    /// (Optional) Time after which the Offer is no longer active, in seconds since the Ripple Epoch.
+    pub expiration: Option<u32>,
+    /// (Optional) An Offer to delete first, specified in the same way as OfferCancel.
+    pub offer_sequence: Option<u32>,
+}
+
+into_transaction!(OfferCreate);
+
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct OfferCancel {
+    /// The sequence number (or Ticket number) of a previous OfferCreate transaction. If specified, cancel any offer object in the ledger that was created by that transaction. It is not considered an error if the offer specified does not exist.
+    pub offer_sequence: u32,
+}
+
+into_transaction!(OfferCancel);
+
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct EscrowCreate {
+    /// Amount of XRP, in drops, to deduct from the sender's balance and escrow. Once escrowed, the XRP can either go to the Destination address (after the FinishAfter time) or returned to the sender (after the CancelAfter time).
+    pub amount: BigInt,
+    /// Address to receive escrowed XRP.
+    pub destination: Address,
+    /// (Optional) The time, in seconds since the Ripple Epoch, when this escrow expires. This value is immutable; the funds can only be returned the sender after this time.
+    pub cancel_after: Option<u32>,
+    /// (Optional) The time, in seconds since the Ripple Epoch, when the escrowed XRP can be released to the recipient. This value is immutable; the funds cannot move until this time is reached.
+    pub finish_after: Option<u32>,
+    /// (Optional) Hex value representing a PREIMAGE-SHA-256 crypto-condition. The funds can only be delivered to the recipient if this condition is fulfilled.
+    pub condition: Option<String>,
+    /// (Optional) Arbitrary tag to further specify the destination for this escrowed payment, such as a hosted recipient at the destination address.
+    pub destination_tag: Option<u32>,
+}
+
+into_transaction!(EscrowCreate);
+
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct EscrowFinish {
+    /// Address of the source account that funded the held payment.
+    pub owner: Address,
+    /// Transaction sequence (or Ticket number) of EscrowCreate transaction that created the held payment to finish.
+    pub offer_sequence: u32,
+    /// (Optional) Hex value matching the previously-supplied PREIMAGE-SHA-256 crypto-condition of the held payment.
+    pub condition: Option<String>,
+    /// (Optional) Hex value of the PREIMAGE-SHA-256 crypto-condition fulfillment matching the held payment's Condition.
+    pub fulfillment: Option<String>,
+}
+
+into_transaction!(EscrowFinish);
+
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct EscrowCancel {
+    /// Address of the source account that funded the escrow payment.
+    pub owner: Address,
+    /// Transaction sequence (or Ticket number) of EscrowCreate transaction that created the escrow to cancel.
+    pub offer_sequence: u32,
+}
+
+into_transaction!(EscrowCancel);
+
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct NFTokenMint {
     /// Indicates the account that issues the token. This value is optional and should only be specified if the account executing the transaction is not the Issuer of the NFToken object. If it is present, the MintAccount field in the AccountRoot of the Issuer field must match the Account. Otherwise, the transaction fails.
@@ -237,3 +696,615 @@ pub struct NFTokenMint {
 }
 
 into_transaction!(NFTokenMint);
+
+pub const TF_MPT_CAN_LOCK: TFFlag = 0x00000002;
+pub const TF_MPT_REQUIRE_AUTH: TFFlag = 0x00000004;
+pub const TF_MPT_CAN_ESCROW: TFFlag = 0x00000008;
+pub const TF_MPT_CAN_TRADE: TFFlag = 0x00000010;
+pub const TF_MPT_CAN_TRANSFER: TFFlag = 0x00000020;
+pub const TF_MPT_CAN_CLAWBACK: TFFlag = 0x00000040;
+
+pub const TF_MPT_UNAUTHORIZE: TFFlag = 0x00000001;
+
+/// Creates a new Multi-Purpose Token issuance (Added by the MPTokensV1 amendment). The bundled
+/// serde-xrpl definitions do not include the MPT fields yet, so this can only be submitted in JSON
+/// form, e.g. with sign_and_submit.
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct MPTokenIssuanceCreate {
+    /// (Optional) Where to put the decimal place when displaying amounts of this MPT. Defaults to 0.
+    pub asset_scale: Option<u8>,
+    /// (Optional) The fee to charge for transferring the token between holders, in tenths of a basis point. Valid values are 0 to 50000 inclusive. Requires TF_MPT_CAN_TRANSFER.
+    pub transfer_fee: Option<u16>,
+    /// (Optional) The maximum amount of this token that can ever be issued, as a quoted integer.
+    pub maximum_amount: Option<String>,
+    /// (Optional) Arbitrary metadata about this issuance, in hex. Limited to 1024 bytes.
+    #[serde(rename = "MPTokenMetadata")]
+    pub mptoken_metadata: Option<String>,
+}
+
+into_transaction!(MPTokenIssuanceCreate);
+
+/// Opts in to holding a Multi-Purpose Token, or, when sent by the issuer with `holder` set,
+/// authorizes a holder of an issuance that requires authorization (Added by the MPTokensV1
+/// amendment). Like MPTokenIssuanceCreate, this can only be submitted in JSON form for now.
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct MPTokenAuthorize {
+    /// The ID of the MPTokenIssuance, as hex.
+    #[serde(rename = "MPTokenIssuanceID")]
+    pub mptoken_issuance_id: String,
+    /// (Optional) The holder to authorize or unauthorize. Only used by the issuer.
+    pub holder: Option<Address>,
+}
+
+into_transaction!(MPTokenAuthorize);
+
+/// Claws back tokens issued by the sending account from a holder (Added by the Clawback
+/// amendment). Clawing back trust line tokens requires the issuer to have enabled
+/// ASF_ALLOW_TRUSTLINE_CLAWBACK before issuing them. The bundled serde-xrpl definitions do not
+/// include Clawback yet, so this can only be submitted in JSON form, e.g. with sign_and_submit.
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Clawback {
+    /// The amount being clawed back, either of trust line tokens or of MPTs, but not XRP. The quantity may be more than the available balance, in which case the full balance is clawed back. For trust line tokens, the issuer subfield is the holder's address, not the issuer's, and must not be the sending account.
+    pub amount: CurrencyAmount,
+    /// (Optional) The holder to claw back MPTs from. Required when clawing back MPTs, and must not be used when clawing back trust line tokens.
+    pub holder: Option<Address>,
+}
+
+into_transaction!(Clawback);
+
+type BatchFlag = u32;
+
+pub const TF_ALL_OR_NOTHING: BatchFlag = 0x00010000;
+pub const TF_ONLY_ONE: BatchFlag = 0x00020000;
+pub const TF_UNTIL_FAILURE: BatchFlag = 0x00040000;
+pub const TF_INDEPENDENT: BatchFlag = 0x00080000;
+
+/// Flag set on transactions wrapped by a Batch transaction.
+pub const TF_INNER_BATCH_TXN: TFFlag = 0x40000000;
+
+/// A transaction wrapping inner transactions that are applied together (Added by the Batch
+/// amendment). The bundled serde-xrpl definitions do not include the Batch fields yet, so this
+/// can only be submitted in JSON form, e.g. with sign_and_submit.
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Batch {
+    /// The inner transactions to apply. Inner transactions have no fee or signature of their own;
+    /// they are authorized by the outer transaction.
+    #[serde(with = "wrapped")]
+    pub raw_transactions: Vec<Transaction>,
+    /// How the inner transactions are applied, one of TF_ALL_OR_NOTHING, TF_ONLY_ONE, TF_UNTIL_FAILURE or TF_INDEPENDENT.
+    pub batch_flags: BatchFlag,
+}
+
+impl Batch {
+    /// Creates a Batch from the provided transactions, clearing the fee and signature fields of
+    /// each and marking them as inner batch transactions.
+    pub fn new(transactions: Vec<Transaction>, batch_flags: BatchFlag) -> Self {
+        let raw_transactions = transactions
+            .into_iter()
+            .map(|mut tx| {
+                tx.fee = BigInt(0);
+                tx.signing_pub_key = String::new();
+                tx.txn_signature = None;
+                tx.flags = Some(tx.flags.unwrap_or_default() | TF_INNER_BATCH_TXN);
+                tx
+            })
+            .collect();
+        Self {
+            raw_transactions,
+            batch_flags,
+        }
+    }
+}
+
+into_transaction!(Batch);
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AccountSet, CheckCancel, CheckCash, CheckCashAmount, EscrowFinish, Memo, OfferCreate,
+        Payment, PaymentChannelClaim, PaymentChannelFund, SetRegularKey, SignerListSet,
+        Transaction, TransactionType, ASF_AUTHORIZED_NFTOKEN_MINTER, ASF_REQUIRE_DEST, TF_CLOSE,
+        TF_PARTIAL_PAYMENT, TF_RENEW, TF_SELL,
+    };
+    use crate::transaction::decode_account_id;
+    use crate::types::{path::PathStep, BigInt, CurrencyAmount, SignerEntry};
+    use crate::wallet::Wallet;
+    use crate::ValidationError;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn extra_fields() {
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        tx.account = "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned();
+        let mut extra = HashMap::new();
+        extra.insert("TicketSequence".to_owned(), json!(5));
+        tx.extra = Some(extra);
+        assert_eq!(tx.to_rippled_json().unwrap()["TicketSequence"], json!(5));
+        let tx_blob = serde_xrpl::ser::to_bytes(&serde_json::to_value(&tx).unwrap()).unwrap();
+        assert!(tx_blob
+            .windows(6)
+            .any(|w| w == [0x20, 0x29, 0x00, 0x00, 0x00, 0x05]));
+        // Names the definitions don't know would be sent but never signed.
+        let mut unknown = tx.clone();
+        unknown.extra = Some(HashMap::from([("NotAField".to_owned(), json!(1))]));
+        assert_eq!(
+            unknown.check_extra(),
+            Err(serde_xrpl::error::Error::UnknownFieldName(
+                "NotAField".to_owned()
+            ))
+        );
+        assert!(unknown.to_rippled_json().is_err());
+        // Fields set through a typed field can't be set again.
+        let mut duplicate = tx;
+        duplicate.extra = Some(HashMap::from([("Destination".to_owned(), json!("r"))]));
+        assert!(duplicate.check_extra().is_err());
+    }
+
+    #[test]
+    fn memos() {
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        tx.account = "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned();
+        tx.memos = Some(vec![Memo {
+            memo_data: Some("72656E74".to_owned()),
+            memo_type: Some("696E766F696365".to_owned()),
+            ..Default::default()
+        }]);
+        let json = tx.to_rippled_json().unwrap();
+        assert_eq!(
+            json["Memos"],
+            json!([{ "Memo": { "MemoData": "72656E74", "MemoType": "696E766F696365" } }])
+        );
+        let tx_blob = serde_xrpl::ser::to_bytes(&serde_json::to_value(&tx).unwrap()).unwrap();
+        let memos = hex::decode("F9EA7C07696E766F6963657D0472656E74E1F1").unwrap();
+        assert!(tx_blob.ends_with(&memos));
+        let tx: Transaction = serde_json::from_value(json).unwrap();
+        assert_eq!(tx.memos.unwrap()[0].memo_data.as_deref(), Some("72656E74"));
+    }
+
+    #[test]
+    fn payment_to_x_address() {
+        let payment = Payment::to_x_address(
+            "XVLhHMPHU98es4dbozjVtdWzVrDjtV8xvjGQTYPiAx6gwDC",
+            CurrencyAmount::xrp(1),
+            false,
+        )
+        .unwrap();
+        assert_eq!(payment.destination, "rGWrZyQqhTp9Xu7G5Pkayo7bXjH4k4QYpf");
+        assert_eq!(payment.destination_tag, Some(1));
+        assert_eq!(
+            payment.into_transaction().to_rippled_json().unwrap()["DestinationTag"],
+            json!(1)
+        );
+        // The same account and a tag of 12345 on a test network.
+        let test_x_address = "TVE26TYGhfLC7tQDno7G8dGtxSkYQn7h4mkJyVSGxTcrDFJ";
+        let payment = Payment::to_x_address(test_x_address, CurrencyAmount::xrp(1), true).unwrap();
+        assert_eq!(payment.destination, "rGWrZyQqhTp9Xu7G5Pkayo7bXjH4k4QYpf");
+        assert_eq!(payment.destination_tag, Some(12345));
+        assert!(matches!(
+            Payment::to_x_address(test_x_address, CurrencyAmount::xrp(1), false),
+            Err(crate::Error::Validation(ValidationError::WrongNetwork(_)))
+        ));
+        assert!(matches!(
+            Payment::to_x_address(
+                "XVLhHMPHU98es4dbozjVtdWzVrDjtV8xvjGQTYPiAx6gwDC",
+                CurrencyAmount::xrp(1),
+                true
+            ),
+            Err(crate::Error::Validation(ValidationError::WrongNetwork(_)))
+        ));
+    }
+
+    #[test]
+    fn payment_validate() {
+        let usd = |value| {
+            CurrencyAmount::issued_currency(
+                Decimal::new(value, 0),
+                "USD",
+                &"rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned(),
+            )
+        };
+        let invalid = |payment: Payment, flags| {
+            matches!(
+                payment.validate(flags),
+                Err(crate::Error::Validation(ValidationError::InvalidAmount(_)))
+            )
+        };
+        let partial = Payment {
+            amount: usd(10),
+            send_max: Some(CurrencyAmount::xrp(20_000_000)),
+            deliver_min: Some(usd(5)),
+            ..Default::default()
+        };
+        partial.validate(Some(TF_PARTIAL_PAYMENT)).unwrap();
+        let json = partial
+            .clone()
+            .into_transaction()
+            .to_rippled_json()
+            .unwrap();
+        assert_eq!(json["SendMax"], json!("20000000"));
+        assert_eq!(json["DeliverMin"]["value"], json!("5"));
+        // DeliverMin is only valid for partial payments, and can't exceed Amount.
+        assert!(invalid(partial.clone(), None));
+        assert!(invalid(
+            Payment {
+                deliver_min: Some(usd(11)),
+                ..partial.clone()
+            },
+            Some(TF_PARTIAL_PAYMENT)
+        ));
+        assert!(invalid(
+            Payment {
+                deliver_min: Some(CurrencyAmount::xrp(5)),
+                ..partial
+            },
+            Some(TF_PARTIAL_PAYMENT)
+        ));
+        // Paths make a payment cross-currency, so SendMax is required.
+        let path = vec![vec![PathStep {
+            currency: Some("XRP".to_owned()),
+            ..Default::default()
+        }]];
+        assert!(invalid(
+            Payment {
+                amount: usd(10),
+                paths: Some(path.clone()),
+                ..Default::default()
+            },
+            None
+        ));
+        Payment {
+            amount: usd(10),
+            send_max: Some(CurrencyAmount::xrp(20_000_000)),
+            paths: Some(path),
+            ..Default::default()
+        }
+        .validate(None)
+        .unwrap();
+        // XRP-to-XRP payments can't have an XRP SendMax or be partial.
+        let xrp = Payment {
+            amount: CurrencyAmount::xrp(10),
+            ..Default::default()
+        };
+        xrp.validate(None).unwrap();
+        assert!(invalid(xrp.clone(), Some(TF_PARTIAL_PAYMENT)));
+        assert!(invalid(
+            Payment {
+                send_max: Some(CurrencyAmount::xrp(10)),
+                ..xrp
+            },
+            None
+        ));
+    }
+
+    #[test]
+    fn cross_currency_payment() {
+        let issuer = "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned();
+        let invoice_id = "6F1DFD1D0FE8A32E40E1F2C05CF1C15545BAB56B617F9C6C2D63A6B704BEF59B";
+        let payment = Payment {
+            amount: CurrencyAmount::xrp(1000000),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            destination_tag: Some(12),
+            send_max: Some(CurrencyAmount::issued_currency(
+                Decimal::new(15, 1),
+                "USD",
+                &issuer,
+            )),
+            invoice_id: Some(invoice_id.to_owned()),
+            source_tag: Some(7),
+            paths: Some(vec![vec![PathStep {
+                currency: Some("XRP".to_owned()),
+                ..Default::default()
+            }]]),
+            ..Default::default()
+        };
+        // Delivering XRP paid for with USD may be partial.
+        payment.validate(Some(TF_PARTIAL_PAYMENT)).unwrap();
+        let json = payment
+            .clone()
+            .into_transaction()
+            .to_rippled_json()
+            .unwrap();
+        assert_eq!(json["InvoiceID"], json!(invoice_id));
+        assert_eq!(json["SourceTag"], json!(7));
+        assert_eq!(json["DestinationTag"], json!(12));
+        assert_eq!(json["SendMax"]["currency"], json!("USD"));
+        assert_eq!(json["Paths"], json!([[{ "currency": "XRP" }]]));
+        let mut tx = Payment {
+            paths: None,
+            ..payment
+        }
+        .into_transaction();
+        tx.account = "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned();
+        let tx_blob = serde_xrpl::ser::to_bytes(&serde_json::to_value(&tx).unwrap()).unwrap();
+        let tx_blob = hex::encode_upper(tx_blob);
+        assert!(tx_blob.contains("2300000007"));
+        assert!(tx_blob.contains(&format!("5011{}", invoice_id)));
+    }
+
+    #[test]
+    fn offer_create() {
+        let mut tx = OfferCreate {
+            taker_gets: CurrencyAmount::xrp(15000000000),
+            taker_pays: CurrencyAmount::issued_currency(
+                Decimal::new(70728, 1),
+                "USD",
+                &"rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned(),
+            ),
+            expiration: Some(595640108),
+            offer_sequence: Some(1752791),
+        }
+        .into_transaction();
+        tx.account = "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned();
+        tx.fee = BigInt(10);
+        tx.flags = Some(TF_SELL);
+        tx.sequence = 1752792;
+        let tx_blob = serde_xrpl::ser::to_bytes(&serde_json::to_value(&tx).unwrap()).unwrap();
+        // The fields of the serde-xrpl OfferCreate fixture, taken from a validated transaction.
+        let expected = [
+            "120007",
+            "220008000024001ABED8",
+            "2A2380BF2C2019001ABED7",
+            "64D55920AC9391400000000000000000000000000055534400000000000A20B3C85F482532A9578DBB3950B85CA06594D1",
+            "65400000037E11D600",
+            "68400000000000000A",
+            "8114DD76483FACDEE26E60D8A586BB58D09F27045C46",
+        ];
+        let tx_blob = hex::encode_upper(tx_blob);
+        assert!(expected.iter().all(|fields| tx_blob.contains(fields)));
+    }
+
+    #[test]
+    fn check_cash() {
+        let check_id = "838766BA2B995C00744175F69A1B11E32C3DBC40E64801A4056FCBD657F57334";
+        let mut tx =
+            CheckCash::at_least(check_id, CurrencyAmount::xrp(100000000)).into_transaction();
+        tx.account = "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned();
+        let json = tx.to_rippled_json().unwrap();
+        assert_eq!(json["CheckID"], json!(check_id));
+        assert_eq!(json["DeliverMin"], json!("100000000"));
+        assert!(json.get("Amount").is_none());
+        let parsed: Transaction = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            parsed.tx,
+            Some(TransactionType::CheckCash(CheckCash {
+                amount: CheckCashAmount::DeliverMin(_),
+                ..
+            }))
+        ));
+        let tx_blob = serde_xrpl::ser::to_bytes(&serde_json::to_value(&tx).unwrap()).unwrap();
+        let tx_blob = hex::encode_upper(tx_blob);
+        assert!(tx_blob.starts_with("120011"));
+        assert!(tx_blob.contains(&format!("5018{}", check_id)));
+        assert!(tx_blob.contains("6A4000000005F5E100"));
+
+        let json = CheckCash::exact(check_id, CurrencyAmount::xrp(1))
+            .into_transaction()
+            .to_rippled_json()
+            .unwrap();
+        assert_eq!(json["Amount"], json!("1"));
+        assert!(json.get("DeliverMin").is_none());
+        let json = CheckCancel {
+            check_id: check_id.to_owned(),
+        }
+        .into_transaction()
+        .to_rippled_json()
+        .unwrap();
+        assert_eq!(json["CheckID"], json!(check_id));
+    }
+
+    #[test]
+    fn payment_channel_fund() {
+        let channel = "C1AE6DDDEEC05CF2978C0BAD6FE302948E9533691DC749DCDD3B9E5992CA6198";
+        let mut tx = PaymentChannelFund {
+            channel: channel.parse().unwrap(),
+            amount: BigInt(200000),
+            expiration: Some(543171558),
+        }
+        .into_transaction();
+        tx.account = "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned();
+        tx.fee = BigInt(10);
+        tx.sequence = 1752792;
+        let tx_blob = serde_xrpl::ser::to_bytes(&serde_json::to_value(&tx).unwrap()).unwrap();
+        let expected = [
+            "12000E",
+            "24001ABED8",
+            "2A206023E6",
+            "5016C1AE6DDDEEC05CF2978C0BAD6FE302948E9533691DC749DCDD3B9E5992CA6198",
+            "614000000000030D40",
+            "68400000000000000A",
+            "8114DD76483FACDEE26E60D8A586BB58D09F27045C46",
+        ];
+        let tx_blob = hex::encode_upper(tx_blob);
+        assert!(expected.iter().all(|fields| tx_blob.contains(fields)));
+        assert_eq!(tx.to_rippled_json().unwrap()["Channel"], json!(channel));
+    }
+
+    #[test]
+    fn payment_channel_claim_flags() {
+        for (flags, expected) in [(TF_CLOSE, "2200020000"), (TF_RENEW, "2200010000")] {
+            let mut tx = PaymentChannelClaim {
+                channel: "C1AE6DDDEEC05CF2978C0BAD6FE302948E9533691DC749DCDD3B9E5992CA6198"
+                    .parse()
+                    .unwrap(),
+                ..Default::default()
+            }
+            .into_transaction();
+            tx.account = "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned();
+            tx.flags = Some(flags);
+            let tx_blob = serde_xrpl::ser::to_bytes(&serde_json::to_value(&tx).unwrap()).unwrap();
+            assert!(hex::encode_upper(tx_blob).starts_with(&format!("12000F{}", expected)));
+        }
+    }
+
+    #[test]
+    fn escrow_finish() {
+        let wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        // A PREIMAGE-SHA-256 condition and fulfillment for an empty preimage.
+        let condition =
+            "A0258020E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855810100";
+        let fulfillment = "A0028000";
+        let mut tx = EscrowFinish {
+            owner: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            offer_sequence: 7,
+            condition: Some(condition.to_owned()),
+            fulfillment: Some(fulfillment.to_owned()),
+        }
+        .into_transaction();
+        tx.account = wallet.address();
+        tx.fee = BigInt(330);
+        let tx_blob = wallet.sign(&mut tx).unwrap();
+        assert!(tx.txn_signature.is_some());
+        assert!(tx_blob.starts_with("120002"));
+        // Both crypto-condition fields are serialized as variable length blobs.
+        assert!(tx_blob.contains(&format!("7010{:02X}{}", fulfillment.len() / 2, fulfillment)));
+        assert!(tx_blob.contains(&format!("7011{:02X}{}", condition.len() / 2, condition)));
+    }
+
+    #[test]
+    fn signer_list_set() {
+        let wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        let signer = "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw";
+        let mut tx = SignerListSet {
+            signer_quorum: 3,
+            signer_entries: vec![
+                SignerEntry {
+                    account: signer.to_owned(),
+                    signer_weight: 2,
+                },
+                SignerEntry {
+                    account: "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned(),
+                    signer_weight: 1,
+                },
+            ],
+        }
+        .into_transaction();
+        tx.account = wallet.address();
+        tx.fee = BigInt(12);
+        let json = tx.to_rippled_json().unwrap();
+        assert_eq!(
+            json["SignerEntries"][0],
+            json!({ "SignerEntry": { "Account": signer, "SignerWeight": 2 } })
+        );
+        assert_eq!(serde_json::from_value::<Transaction>(json).unwrap(), tx);
+        let tx_blob = wallet.sign(&mut tx).unwrap();
+        assert!(tx_blob.starts_with("12000C"));
+        assert!(tx_blob.contains("202300000003"));
+        // An STArray of SignerEntry objects, each with its SignerWeight and Account.
+        let entry = format!(
+            "F4EB13000281{:02X}{}E1",
+            20,
+            hex::encode_upper(decode_account_id(signer).unwrap())
+        );
+        assert!(tx_blob.contains(&entry));
+        // Deleting the list omits the signer entries.
+        let json = SignerListSet::delete()
+            .into_transaction()
+            .to_rippled_json()
+            .unwrap();
+        assert_eq!(json["SignerQuorum"], json!(0));
+        assert!(json.get("SignerEntries").is_none());
+    }
+
+    #[test]
+    fn set_regular_key() {
+        let json = SetRegularKey {
+            regular_key: Some("rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned()),
+        }
+        .into_transaction()
+        .to_rippled_json()
+        .unwrap();
+        assert_eq!(json["TransactionType"], json!("SetRegularKey"));
+        assert_eq!(
+            json["RegularKey"],
+            json!("rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw")
+        );
+        let json = SetRegularKey::default()
+            .into_transaction()
+            .to_rippled_json()
+            .unwrap();
+        assert!(json.get("RegularKey").is_none());
+    }
+
+    #[test]
+    fn to_rippled_json() {
+        let payment = Payment {
+            amount: CurrencyAmount::issued_currency(
+                Decimal::new(15, 1),
+                "USD",
+                &"rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned(),
+            ),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        };
+        let mut tx = payment.into_transaction();
+        tx.account = "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned();
+        tx.fee = BigInt(12);
+        tx.sequence = 1752792;
+        tx.last_ledger_sequence = 1752812;
+        tx.signing_pub_key =
+            "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3".to_owned();
+        tx.flags = Some(2147483648);
+        assert_eq!(
+            tx.to_rippled_json().unwrap(),
+            json!({
+                "TransactionType": "Payment",
+                "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+                "Amount": {
+                    "currency": "USD",
+                    "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+                    "value": "1.5"
+                },
+                "Destination": "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw",
+                "Fee": "12",
+                "Flags": 2147483648u32,
+                "LastLedgerSequence": 1752812,
+                "Sequence": 1752792,
+                "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3"
+            })
+        );
+        let tx: Transaction = serde_json::from_value(tx.to_rippled_json().unwrap()).unwrap();
+        assert_eq!(tx.fee, BigInt(12));
+    }
+
+    #[test]
+    fn account_set_flags() {
+        let account_set = AccountSet {
+            nftoken_minter: Some("rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned()),
+            ..Default::default()
+        }
+        .enable(ASF_AUTHORIZED_NFTOKEN_MINTER)
+        .disable(ASF_REQUIRE_DEST);
+        assert_eq!(account_set.set_flag, Some(10));
+        assert_eq!(account_set.clear_flag, Some(1));
+        assert_eq!(
+            account_set.into_transaction().to_rippled_json().unwrap(),
+            json!({
+                "TransactionType": "AccountSet",
+                "Account": "",
+                "Fee": "0",
+                "Sequence": 0,
+                "LastLedgerSequence": 0,
+                "SigningPubKey": "",
+                "SetFlag": 10,
+                "ClearFlag": 1,
+                "NFTokenMinter": "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw"
+            })
+        );
+    }
+}
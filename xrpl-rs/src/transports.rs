@@ -1,30 +1,43 @@
 use super::types::{
+    path::{PathFindRequest, PathFindResponse},
     subscribe::{SubscribeRequest, SubscriptionEvent},
     ErrorResponse, JsonRPCResponse, JsonRPCResponseResult, RequestId, WebsocketResponse,
 };
 use async_trait::async_trait;
 use futures::{channel::mpsc, SinkExt, Stream, StreamExt};
-use reqwest::{header::CONTENT_TYPE, Client};
+use reqwest::{header::CONTENT_TYPE, Client, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpStream;
 use tokio_tungstenite::{
-    connect_async,
-    tungstenite::{Error as WSError, Message, Result},
+    connect_async_with_config,
+    tungstenite::{
+        error::CapacityError, protocol::WebSocketConfig, Error as WSError, Message, Result,
+    },
+    MaybeTlsStream, WebSocketStream,
 };
 use url::{ParseError, Url};
 
 #[async_trait]
 pub trait Transport {
+    /// Sends a request and waits for its response. Dropping the returned future cancels the
+    /// request; HTTP requests are aborted and WebSocket requests stop waiting for a response.
     async fn send_request<Params: Serialize + Send, Res: DeserializeOwned + Debug + Send>(
         &self,
         method: &str,
         params: Params,
     ) -> Result<Res, TransportError>;
+    /// Whether this transport can deliver subscription streams, i.e. also implements
+    /// `DuplexTransport`.
+    fn supports_subscriptions(&self) -> bool {
+        false
+    }
 }
 
 #[async_trait]
@@ -37,6 +50,15 @@ pub trait DuplexTransport: Transport {
         TransportError,
     >;
     async fn unsubscribe(&self, request: SubscribeRequest) -> Result<(), TransportError>;
+    /// Opens a pathfinding request with the `create` subcommand, returning the paths found and an
+    /// update each time they change until the request is closed.
+    async fn path_find(
+        &self,
+        request: PathFindRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<PathFindResponse, TransportError>>>>, TransportError>;
+    /// Closes the open pathfinding request with the `close` subcommand, ending its stream and
+    /// returning the final paths found.
+    async fn path_find_close(&self) -> Result<PathFindResponse, TransportError>;
 }
 
 #[derive(Debug)]
@@ -49,10 +71,24 @@ pub enum TransportError {
     WSError(WSError),
     ErrorResponse(String),
     APIError(ErrorResponse),
+    Timeout,
+    ConnectionClosed,
+    ResponseTooLarge,
+    /// The WebSocket connection was lost before the request was answered.
+    Disconnected,
 }
 
+/// The default time to wait for a response to a request.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The default time to wait before the first retry of a failed HTTP request.
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
 impl From<reqwest::Error> for TransportError {
     fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            return Self::Timeout;
+        }
         Self::ReqwestError(e)
     }
 }
@@ -79,16 +115,66 @@ pub struct WebSocketRPCRequest<T: Serialize + Send> {
 
 unsafe impl<T: Serialize + Send> Send for JsonRPCRequest<T> {}
 
+/// Builds an endpoint URL from its parts. Hosts given with a trailing slash are accepted.
+fn host_url(scheme: &str, host: &str, port: u16) -> Result<Url, TransportError> {
+    let host = host.trim_end_matches('/');
+    Url::parse(&format!("{}://{}:{}/", scheme, host, port))
+        .map_err(|e| TransportError::InvalidEndpoint(e))
+}
+
 pub struct HTTP {
     counter: AtomicU64,
     inner: Client,
     base_url: Url,
+    max_response_bytes: Option<usize>,
+    retries: u32,
+    retry_backoff: Duration,
 }
 
 impl HTTP {
     pub fn builder() -> HTTPBuilder {
         HTTPBuilder::default()
     }
+    // Posts a serialized JSON-RPC request once and parses its response.
+    async fn post<Res: DeserializeOwned>(&self, body: &str) -> Result<Res, TransportError> {
+        let client = self.inner.clone();
+        let res = client
+            .post(self.base_url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_owned())
+            .send()
+            .await?;
+        if res.status() == StatusCode::SERVICE_UNAVAILABLE {
+            return Err(res.error_for_status().unwrap_err().into());
+        }
+        let json = match self.max_response_bytes {
+            Some(max_response_bytes) => {
+                let body = read_limited_body(res, max_response_bytes).await?;
+                serde_json::from_slice::<JsonRPCResponse<Res>>(&body)
+                    .map_err(|e| TransportError::JSONError(e))?
+            }
+            None => res.json::<JsonRPCResponse<Res>>().await?,
+        };
+        match json.result {
+            JsonRPCResponseResult::Success(success) => Ok(success.result),
+            JsonRPCResponseResult::Error(e) => Err(TransportError::APIError(e)),
+        }
+    }
+}
+
+// Whether a request that failed with the error may succeed if sent again. Errors returned by the
+// API are deterministic, so only failures to reach the server or read its response are retried.
+fn is_retryable(e: &TransportError) -> bool {
+    match e {
+        TransportError::Timeout => true,
+        TransportError::ReqwestError(e) => {
+            e.is_connect()
+                || e.is_request()
+                || e.is_body()
+                || e.status() == Some(StatusCode::SERVICE_UNAVAILABLE)
+        }
+        _ => false,
+    }
 }
 
 #[async_trait]
@@ -103,24 +189,48 @@ impl Transport for HTTP {
             params: vec![params],
         })
         .map_err(|e| TransportError::JSONError(e))?;
-        let client = self.inner.clone();
-        let res = client
-            .post(self.base_url.clone())
-            .header(CONTENT_TYPE, "application/json")
-            .body(json_str)
-            .send()
-            .await?;
-        let json = res.json::<JsonRPCResponse<Res>>().await;
-        match json.map_err(|e| TransportError::ReqwestError(e))?.result {
-            JsonRPCResponseResult::Success(success) => Ok(success.result),
-            JsonRPCResponseResult::Error(e) => Err(TransportError::APIError(e)),
+        let mut attempt = 0;
+        loop {
+            match self.post(&json_str).await {
+                Err(e) if attempt < self.retries && is_retryable(&e) => {
+                    // The backoff doubles after each failed attempt.
+                    let backoff = self.retry_backoff.saturating_mul(1 << attempt.min(16));
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+/// Reads a response body, failing with `TransportError::ResponseTooLarge` as soon as more than
+/// `max_response_bytes` have been received.
+async fn read_limited_body(
+    mut res: reqwest::Response,
+    max_response_bytes: usize,
+) -> Result<Vec<u8>, TransportError> {
+    if res.content_length().unwrap_or(0) > max_response_bytes as u64 {
+        return Err(TransportError::ResponseTooLarge);
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = res.chunk().await? {
+        if body.len() + chunk.len() > max_response_bytes {
+            return Err(TransportError::ResponseTooLarge);
         }
+        body.extend_from_slice(&chunk);
     }
+    Ok(body)
 }
 
 #[derive(Default)]
 pub struct HTTPBuilder {
     pub endpoint: Option<Url>,
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+    pub max_response_bytes: Option<usize>,
+    pub retries: u32,
+    pub retry_backoff: Option<Duration>,
 }
 
 impl HTTPBuilder {
@@ -130,11 +240,65 @@ impl HTTPBuilder {
         Ok(self)
     }
 
+    /// Sets the endpoint from a host and port, using `https` when `secure` is true and `http`
+    /// otherwise.
+    pub fn with_host<'b>(
+        &'b mut self,
+        host: &str,
+        port: u16,
+        secure: bool,
+    ) -> Result<&'b mut Self, TransportError> {
+        self.endpoint = Some(host_url(if secure { "https" } else { "http" }, host, port)?);
+        Ok(self)
+    }
+
+    /// Sets how long to wait for each request to complete, including reading the response body,
+    /// before failing with `TransportError::Timeout`. Defaults to `DEFAULT_REQUEST_TIMEOUT`.
+    pub fn with_timeout<'b>(&'b mut self, timeout: Duration) -> &'b mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long to wait for a connection to the endpoint to be established. By default only
+    /// the request timeout applies.
+    pub fn with_connect_timeout<'b>(&'b mut self, connect_timeout: Duration) -> &'b mut Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Limits the size of response bodies. Larger responses fail with
+    /// `TransportError::ResponseTooLarge` instead of being read into memory.
+    pub fn with_max_response_bytes<'b>(&'b mut self, max_response_bytes: usize) -> &'b mut Self {
+        self.max_response_bytes = Some(max_response_bytes);
+        self
+    }
+
+    /// Retries requests up to `max` times when the server can't be reached, times out or is
+    /// unavailable. Error responses from the API are never retried. Defaults to no retries.
+    pub fn with_retries<'b>(&'b mut self, max: u32) -> &'b mut Self {
+        self.retries = max;
+        self
+    }
+
+    /// Sets how long to wait before the first retry, doubling for each further retry. Defaults to
+    /// `DEFAULT_RETRY_BACKOFF`.
+    pub fn with_retry_backoff<'b>(&'b mut self, retry_backoff: Duration) -> &'b mut Self {
+        self.retry_backoff = Some(retry_backoff);
+        self
+    }
+
     pub fn build(&self) -> Result<HTTP, TransportError> {
+        let mut client = Client::builder().timeout(self.timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT));
+        if let Some(connect_timeout) = self.connect_timeout {
+            client = client.connect_timeout(connect_timeout);
+        }
         Ok(HTTP {
             counter: AtomicU64::new(0u64),
             base_url: self.endpoint.clone().ok_or(TransportError::NoEndpoint)?,
-            inner: Client::new(),
+            inner: client.build()?,
+            max_response_bytes: self.max_response_bytes,
+            retries: self.retries,
+            retry_backoff: self.retry_backoff.unwrap_or(DEFAULT_RETRY_BACKOFF),
         })
     }
 }
@@ -148,7 +312,22 @@ pub enum Outbound {
 pub struct PendingRequest {
     id: RequestId,
     request: WebSocketRPCRequest<Value>,
-    response: mpsc::Sender<WebsocketResponse<Value>>,
+    response: mpsc::Sender<Result<WebsocketResponse<Value>, TransportError>>,
+}
+
+/// Removes a pending request from the pending requests when dropped, so that a request whose
+/// future is dropped before it is answered, e.g. to cancel it, is not left pending.
+struct PendingRequestGuard<'a> {
+    id: u64,
+    pending_requests: &'a Mutex<HashMap<u64, PendingRequest>>,
+}
+
+impl Drop for PendingRequestGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut pending_requests) = self.pending_requests.lock() {
+            pending_requests.remove(&self.id);
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -162,6 +341,7 @@ pub struct WebSocket {
     sender: mpsc::UnboundedSender<Outbound>,
     pending_requests: Arc<Mutex<HashMap<u64, PendingRequest>>>,
     subscriptions: Arc<Mutex<Vec<Subscription>>>,
+    timeout: Duration,
 }
 
 impl WebSocket {
@@ -171,11 +351,54 @@ impl WebSocket {
             sender,
             pending_requests: Arc::new(Mutex::new(HashMap::new())),
             subscriptions: Arc::new(Mutex::new(Vec::new())),
+            timeout: DEFAULT_REQUEST_TIMEOUT,
         }
     }
     pub fn builder() -> WebSocketBuilder {
         WebSocketBuilder::default()
     }
+    // Sends a request whose results are delivered asynchronously, returning the channel the
+    // results are sent to.
+    async fn open_subscription(
+        &self,
+        command: &str,
+        params: Value,
+    ) -> Result<mpsc::UnboundedReceiver<Result<SubscriptionEvent, TransportError>>, TransportError>
+    {
+        let mut sender = self.sender.clone();
+        let id = self.counter.fetch_add(1u64, Ordering::Relaxed);
+        let (s, r) = mpsc::unbounded();
+        let req = Subscription {
+            request: WebSocketRPCRequest {
+                id,
+                command: command.to_owned(),
+                params,
+            },
+            channel: s.clone(),
+        };
+        if let Ok(mut subs) = self.subscriptions.lock() {
+            subs.push(req.clone());
+        }
+        sender
+            .send(Outbound::Subscription(req))
+            .await
+            .map_err(|e| TransportError::ErrorResponse(format!("sending: {:?}", e)))?; //TODO: Add error type for websocket send error
+        Ok(r)
+    }
+    // Forgets the subscriptions opened with the given command and parameters, closing their
+    // channels to end their streams, so they are not replayed after a reconnect.
+    fn close_subscriptions(&self, command: &str, params: Option<&Value>) {
+        if let Ok(mut subs) = self.subscriptions.lock() {
+            subs.retain(|sub| {
+                let matches = sub.request.command == command
+                    && params.map_or(true, |params| &sub.request.params == params);
+                if matches {
+                    sub.channel.close_channel();
+                }
+                !matches
+            });
+        }
+    }
 }
 
 #[async_trait]
@@ -187,7 +410,7 @@ impl Transport for WebSocket {
     ) -> Result<Res, TransportError> {
         let mut sender = self.sender.clone();
         let id = self.counter.fetch_add(1u64, Ordering::Relaxed);
-        let (s, r) = mpsc::channel(1);
+        let (s, mut r) = mpsc::channel(1);
         let request = PendingRequest {
             id,
             request: WebSocketRPCRequest {
@@ -200,24 +423,29 @@ impl Transport for WebSocket {
         if let Ok(mut pending_requests) = self.pending_requests.lock() {
             pending_requests.insert(id, request.clone());
         }
+        // Removes the request once it is answered, times out or its future is dropped.
+        let _pending = PendingRequestGuard {
+            id,
+            pending_requests: &self.pending_requests,
+        };
         sender
             .send(Outbound::PendingRequest(request))
             .await
             .map_err(|e| TransportError::ErrorResponse(format!("sending: {:?}", e)))?; //TODO: Add error type for websocket send error
-        let response: WebsocketResponse<Value> = r
-            .take(1)
-            .collect::<Vec<WebsocketResponse<Value>>>()
-            .await
-            .first()
-            .unwrap()
-            .clone();
-        match response {
+        let response = tokio::time::timeout(self.timeout, r.next()).await;
+        match response
+            .map_err(|_| TransportError::Timeout)?
+            .ok_or(TransportError::ConnectionClosed)??
+        {
             WebsocketResponse::Success(success) => {
-                Ok(serde_json::from_value(success.result).unwrap())
+                serde_json::from_value(success.result).map_err(|e| TransportError::JSONError(e))
             }
             WebsocketResponse::Error(e) => Err(TransportError::APIError(e)),
         }
     }
+    fn supports_subscriptions(&self) -> bool {
+        true
+    }
 }
 
 #[async_trait]
@@ -229,34 +457,48 @@ impl DuplexTransport for WebSocket {
         Pin<Box<dyn Stream<Item = Result<SubscriptionEvent, TransportError>>>>,
         TransportError,
     > {
-        let mut sender = self.sender.clone();
-        let id = self.counter.fetch_add(1u64, Ordering::Relaxed);
-        let (s, r) = mpsc::unbounded();
-        let req = Subscription {
-            request: WebSocketRPCRequest {
-                id,
-                command: "subscribe".to_owned(),
-                params: json!(request),
-            },
-            channel: s.clone(),
-        };
-        if let Ok(mut subs) = self.subscriptions.lock() {
-            subs.push(req.clone());
-        }
-        sender
-            .send(Outbound::Subscription(req))
-            .await
-            .map_err(|e| TransportError::ErrorResponse(format!("sending: {:?}", e)))?; //TODO: Add error type for websocket send error
-        Ok(Box::pin(r))
+        Ok(Box::pin(
+            self.open_subscription("subscribe", json!(request)).await?,
+        ))
     }
-    async fn unsubscribe(&self, _request: SubscribeRequest) -> Result<(), TransportError> {
-        Err(TransportError::Error("test"))
+    async fn unsubscribe(&self, request: SubscribeRequest) -> Result<(), TransportError> {
+        let params = json!(request);
+        self.send_request::<_, Value>("unsubscribe", &params)
+            .await?;
+        // Closing the channel ends the stream returned by the matching subscribe call.
+        self.close_subscriptions("subscribe", Some(&params));
+        Ok(())
+    }
+    async fn path_find(
+        &self,
+        request: PathFindRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<PathFindResponse, TransportError>>>>, TransportError>
+    {
+        // Creating a pathfinding request closes the previous one, so its stream ends.
+        self.close_subscriptions("path_find", None);
+        let events = self.open_subscription("path_find", json!(request)).await?;
+        Ok(Box::pin(events.filter_map(|event| {
+            futures::future::ready(match event {
+                Ok(SubscriptionEvent::PathFind(paths)) => Some(Ok(paths)),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            })
+        })))
+    }
+    async fn path_find_close(&self) -> Result<PathFindResponse, TransportError> {
+        let paths = self
+            .send_request("path_find", PathFindRequest::close())
+            .await?;
+        self.close_subscriptions("path_find", None);
+        Ok(paths)
     }
 }
 
 #[derive(Default)]
 pub struct WebSocketBuilder {
     pub endpoint: Option<Url>,
+    pub timeout: Option<Duration>,
+    pub max_response_bytes: Option<usize>,
 }
 
 impl WebSocketBuilder {
@@ -266,58 +508,523 @@ impl WebSocketBuilder {
         Ok(self)
     }
 
+    /// Sets the endpoint from a host and port, using `wss` when `secure` is true and `ws`
+    /// otherwise.
+    pub fn with_host<'b>(
+        &'b mut self,
+        host: &str,
+        port: u16,
+        secure: bool,
+    ) -> Result<&'b mut Self, TransportError> {
+        self.endpoint = Some(host_url(if secure { "wss" } else { "ws" }, host, port)?);
+        Ok(self)
+    }
+
+    /// Sets how long to wait for a response to each request before failing with
+    /// `TransportError::Timeout`. Defaults to `DEFAULT_REQUEST_TIMEOUT`.
+    pub fn with_timeout<'b>(&'b mut self, timeout: Duration) -> &'b mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Limits the size of incoming messages. When a larger message is received the connection is
+    /// dropped and pending requests fail with `TransportError::ResponseTooLarge`.
+    pub fn with_max_response_bytes<'b>(&'b mut self, max_response_bytes: usize) -> &'b mut Self {
+        self.max_response_bytes = Some(max_response_bytes);
+        self
+    }
+
+    /// Connects to the endpoint. If the connection is lost it is reestablished with backoff and
+    /// subscriptions are replayed, so their streams resume. Requests awaiting a response when the
+    /// connection is lost fail with `TransportError::Disconnected`, while requests made during
+    /// reconnection are sent once reconnected.
     pub async fn build(&self) -> Result<WebSocket, TransportError> {
-        let (ws_stream, _) = connect_async(self.endpoint.clone().unwrap()).await?;
+        let endpoint = self.endpoint.clone().ok_or(TransportError::NoEndpoint)?;
+        let config = self
+            .max_response_bytes
+            .map(|max_response_bytes| WebSocketConfig {
+                max_message_size: Some(max_response_bytes),
+                max_frame_size: Some(max_response_bytes),
+                ..Default::default()
+            });
+        let (connection, _) = connect_async_with_config(endpoint.clone(), config).await?;
         let (sender, receiver) = mpsc::unbounded::<Outbound>();
-        let (write, read) = ws_stream.split();
-        let ws = WebSocket::new(sender);
-        let pending_requests = ws.pending_requests.clone();
-        let subscriptions = ws.subscriptions.clone();
-        tokio::spawn(async move {
-            read.for_each(|message| async {
-                let data = message.unwrap().into_data();
-                if data.len() == 0 {
-                    return;
-                }
-                let res: Option<WebsocketResponse<Value>> = serde_json::from_slice(&data).ok();
-                match res {
-                    Some(res) => {
-                        let pr = pending_requests
-                            .lock()
-                            .map(|p| p.get(&res.get_id().unwrap()).and_then(|p|Some(p.clone()))).unwrap();
-                        if let Some(pending_request) = pr {
-                            let mut r = pending_request.response.clone();
-                            r.send(res).await.unwrap();
-                        }
+        let mut ws = WebSocket::new(sender);
+        if let Some(timeout) = self.timeout {
+            ws.timeout = timeout;
+        }
+        tokio::spawn(run_connection(
+            connection,
+            endpoint,
+            config,
+            receiver,
+            ws.pending_requests.clone(),
+            ws.subscriptions.clone(),
+        ));
+        Ok(ws)
+    }
+}
+
+type Connection = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// The longest time to wait between attempts to reconnect a WebSocket.
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
+// Sends outbound requests and handles incoming messages until the WebSocket is dropped,
+// reconnecting whenever the connection is lost.
+async fn run_connection(
+    mut connection: Connection,
+    endpoint: Url,
+    config: Option<WebSocketConfig>,
+    mut outbound: mpsc::UnboundedReceiver<Outbound>,
+    pending_requests: Arc<Mutex<HashMap<u64, PendingRequest>>>,
+    subscriptions: Arc<Mutex<Vec<Subscription>>>,
+) {
+    // Subscriptions opened while disconnected are replayed on reconnection, so they must not be
+    // sent again when their queued request is reached.
+    let mut replayed = HashSet::new();
+    loop {
+        let (mut write, mut read) = connection.split();
+        loop {
+            tokio::select! {
+                message = read.next() => match message {
+                    Some(Ok(message)) if message.is_text() || message.is_binary() => {
+                        handle_message(message.into_data(), &pending_requests, &subscriptions)
+                            .await
                     }
-                    None => {
-                        let subs = subscriptions.lock().unwrap().clone();
-                        for sub in &subs {
-                            let event = serde_json::from_slice::<SubscriptionEvent>(&data)
-                                .map_err(|e| TransportError::JSONError(e));
-                            let mut ch = sub.channel.clone();
-                            ch.send(event).await.unwrap();
+                    // Pings are answered by the stream itself.
+                    Some(Ok(_)) => {}
+                    // The oversized message can't be attributed to a request, so fail every
+                    // pending request rather than leaving them to time out.
+                    Some(Err(WSError::Capacity(CapacityError::MessageTooLong { .. }))) => {
+                        fail_pending(&pending_requests, || TransportError::ResponseTooLarge).await
+                    }
+                    _ => break,
+                },
+                request = outbound.next() => {
+                    let request = match request {
+                        Some(Outbound::PendingRequest(req)) => {
+                            // Skip requests that timed out or were cancelled while queued.
+                            match pending_requests.lock() {
+                                Ok(p) if p.contains_key(&req.id) => req.request,
+                                _ => continue,
+                            }
+                        }
+                        Some(Outbound::Subscription(sub)) if replayed.remove(&sub.request.id) => {
+                            continue
                         }
+                        Some(Outbound::Subscription(sub)) => sub.request,
+                        None => return,
+                    };
+                    let message = Message::Text(serde_json::to_string(&request).unwrap());
+                    if write.send(message).await.is_err() {
+                        break;
                     }
                 }
-            })
+            }
+        }
+        // Requests sent on the lost connection will never be answered.
+        fail_pending(&pending_requests, || TransportError::Disconnected).await;
+        connection = match reconnect(&endpoint, config, &pending_requests).await {
+            Some(connection) => connection,
+            None => return,
+        };
+        let subs = match subscriptions.lock() {
+            Ok(mut subs) => {
+                subs.retain(|sub| !sub.channel.is_closed());
+                subs.clone()
+            }
+            Err(_) => return,
+        };
+        replayed.clear();
+        for sub in subs {
+            replayed.insert(sub.request.id);
+            let message = Message::Text(serde_json::to_string(&sub.request).unwrap());
+            if connection.send(message).await.is_err() {
+                break;
+            }
+        }
+    }
+}
+
+// Connects to the endpoint, doubling the wait after each failed attempt. Returns `None` once the
+// WebSocket has been dropped, as nothing is left to use the connection.
+async fn reconnect(
+    endpoint: &Url,
+    config: Option<WebSocketConfig>,
+    pending_requests: &Arc<Mutex<HashMap<u64, PendingRequest>>>,
+) -> Option<Connection> {
+    let mut backoff = DEFAULT_RETRY_BACKOFF;
+    while Arc::strong_count(pending_requests) > 1 {
+        if let Ok((connection, _)) = connect_async_with_config(endpoint.clone(), config).await {
+            return Some(connection);
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = backoff.saturating_mul(2).min(MAX_RECONNECT_BACKOFF);
+    }
+    None
+}
+
+// Fails every pending request with the error.
+async fn fail_pending(
+    pending_requests: &Mutex<HashMap<u64, PendingRequest>>,
+    error: fn() -> TransportError,
+) {
+    let pending: Vec<PendingRequest> = match pending_requests.lock() {
+        Ok(mut p) => p.drain().map(|(_, p)| p).collect(),
+        Err(_) => return,
+    };
+    for pending_request in pending {
+        let mut r = pending_request.response.clone();
+        let _ = r.send(Err(error())).await;
+    }
+}
+
+// Passes a response to the request it answers, or an event to the subscriptions.
+async fn handle_message(
+    data: Vec<u8>,
+    pending_requests: &Mutex<HashMap<u64, PendingRequest>>,
+    subscriptions: &Mutex<Vec<Subscription>>,
+) {
+    if data.is_empty() {
+        return;
+    }
+    let res: Option<WebsocketResponse<Value>> = serde_json::from_slice(&data).ok();
+    match res {
+        Some(res) => {
+            // Responses are matched to requests by id and each request is removed once answered,
+            // so concurrent requests can't receive each other's responses.
+            let pr = match (res.get_id(), pending_requests.lock()) {
+                (Some(id), Ok(mut p)) => p.remove(&id),
+                _ => None,
+            };
+            if let Some(pending_request) = pr {
+                let mut r = pending_request.response.clone();
+                // The requester may have timed out and dropped the receiver.
+                let _ = r.send(Ok(res)).await;
+                return;
+            }
+            // Otherwise this is the response to a subscription, so errors are passed to its
+            // stream, as are the initial paths found by path_find.
+            let sub = match (res.get_id(), subscriptions.lock()) {
+                (Some(id), Ok(subs)) => subs.iter().find(|sub| sub.request.id == id).cloned(),
+                _ => None,
+            };
+            let sub = match sub {
+                Some(sub) => sub,
+                None => return,
+            };
+            let event = match res {
+                WebsocketResponse::Error(e) => Err(TransportError::APIError(e)),
+                WebsocketResponse::Success(success) if sub.request.command == "path_find" => {
+                    serde_json::from_value(success.result)
+                        .map(SubscriptionEvent::PathFind)
+                        .map_err(|e| TransportError::JSONError(e))
+                }
+                _ => return,
+            };
+            let _ = sub.channel.clone().send(event).await;
+        }
+        None => {
+            let subs = match subscriptions.lock() {
+                Ok(mut subs) => {
+                    // Forget subscriptions whose streams have been dropped.
+                    subs.retain(|sub| !sub.channel.is_closed());
+                    subs.clone()
+                }
+                Err(_) => return,
+            };
+            // Pathfinding updates only go to the pathfinding request, and stream events only to
+            // the subscriptions.
+            let is_path_find = serde_json::from_slice::<Value>(&data)
+                .map_or(false, |event| event["type"] == "path_find");
+            let command = if is_path_find {
+                "path_find"
+            } else {
+                "subscribe"
+            };
+            for sub in subs.iter().filter(|sub| sub.request.command == command) {
+                let event = serde_json::from_slice::<SubscriptionEvent>(&data)
+                    .map_err(|e| TransportError::JSONError(e));
+                let mut ch = sub.channel.clone();
+                let _ = ch.send(event).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        handle_message, DuplexTransport, Outbound, Transport, TransportError, WebSocket, HTTP,
+    };
+    use crate::types::path::PathFindRequest;
+    use crate::types::subscribe::{SubscribeRequest, SubscriptionEvent};
+    use futures::{channel::mpsc, SinkExt, StreamExt};
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    #[tokio::test]
+    async fn dropped_request_is_not_left_pending() {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let ws = WebSocket::new(sender);
+        // The request is never answered, so its future is dropped while it is still pending.
+        let request = ws.send_request::<Value, Value>("ledger_data", json!({}));
+        assert!(tokio::time::timeout(Duration::from_millis(10), request)
+            .await
+            .is_err());
+        assert!(matches!(
+            receiver.next().await,
+            Some(Outbound::PendingRequest(_))
+        ));
+        assert!(ws.pending_requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_get_their_own_responses() {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let ws = WebSocket::new(sender);
+        let requests = futures::future::join_all(
+            (0..100)
+                .map(|n| ws.send_request::<Value, Value>("ledger", json!({ "ledger_index": n }))),
+        );
+        let respond = async {
+            let mut pending = Vec::new();
+            while pending.len() < 100 {
+                match receiver.next().await {
+                    Some(Outbound::PendingRequest(request)) => pending.push(request),
+                    _ => panic!("expected a request"),
+                }
+            }
+            // Each response echoes the ledger index of its request, and they arrive in the
+            // reverse order of the requests.
+            for request in pending.into_iter().rev() {
+                let res = json!({
+                    "id": request.id,
+                    "status": "success",
+                    "type": "response",
+                    "result": { "ledger_index": request.request.params["ledger_index"] }
+                });
+                handle_message(
+                    res.to_string().into_bytes(),
+                    &ws.pending_requests,
+                    &ws.subscriptions,
+                )
+                .await;
+            }
+        };
+        let (responses, _) = futures::join!(requests, respond);
+        for (n, res) in responses.into_iter().enumerate() {
+            assert_eq!(res.unwrap(), json!({ "ledger_index": n }));
+        }
+        assert!(ws.pending_requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn unsubscribe_ends_stream() {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let ws = WebSocket::new(sender);
+        let request = SubscribeRequest::streams(vec!["ledger".to_owned()]);
+        let mut events = ws.subscribe(request.clone()).await.unwrap();
+        let channel = match receiver.next().await {
+            Some(Outbound::Subscription(sub)) => sub.channel,
+            _ => panic!("expected a subscription"),
+        };
+        for ledger_index in 1..=3 {
+            let event = serde_json::from_value(json!({
+                "type": "ledgerClosed",
+                "ledger_hash": "",
+                "ledger_index": ledger_index
+            }))
+            .unwrap();
+            channel.unbounded_send(Ok(event)).unwrap();
+            assert!(matches!(events.next().await, Some(Ok(_))));
+        }
+        let respond = async {
+            let pending = match receiver.next().await {
+                Some(Outbound::PendingRequest(pending)) => pending,
+                _ => panic!("expected the unsubscribe request"),
+            };
+            assert_eq!(pending.request.command, "unsubscribe");
+            assert_eq!(pending.request.params, json!({"streams": ["ledger"]}));
+            let res = serde_json::from_value(json!({
+                "id": pending.id,
+                "status": "success",
+                "type": "response",
+                "result": {}
+            }))
+            .unwrap();
+            pending.response.clone().send(Ok(res)).await.unwrap();
+        };
+        let (unsubscribed, _) = futures::join!(ws.unsubscribe(request), respond);
+        unsubscribed.unwrap();
+        assert!(events.next().await.is_none());
+        assert!(ws.subscriptions.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn path_find_close_ends_stream() {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let ws = WebSocket::new(sender);
+        let request = SubscribeRequest::streams(vec!["ledger".to_owned()]);
+        let mut events = ws.subscribe(request).await.unwrap();
+        let mut paths = ws.path_find(PathFindRequest::default()).await.unwrap();
+        for _ in 0..2 {
+            assert!(matches!(
+                receiver.next().await,
+                Some(Outbound::Subscription(_))
+            ));
+        }
+        // Pathfinding updates and stream events each only reach their own stream.
+        let update = json!({"type": "path_find", "full_reply": true});
+        handle_message(
+            update.to_string().into_bytes(),
+            &ws.pending_requests,
+            &ws.subscriptions,
+        )
+        .await;
+        let ledger = json!({"type": "ledgerClosed", "ledger_hash": "", "ledger_index": 1});
+        handle_message(
+            ledger.to_string().into_bytes(),
+            &ws.pending_requests,
+            &ws.subscriptions,
+        )
+        .await;
+        assert!(matches!(paths.next().await, Some(Ok(paths)) if paths.full_reply == Some(true)));
+        assert!(matches!(
+            events.next().await,
+            Some(Ok(SubscriptionEvent::LedgerClosed(_)))
+        ));
+        let respond = async {
+            let pending = match receiver.next().await {
+                Some(Outbound::PendingRequest(pending)) => pending,
+                _ => panic!("expected the path_find close request"),
+            };
+            assert_eq!(pending.request.params["subcommand"], json!("close"));
+            let res = serde_json::from_value(json!({
+                "id": pending.id,
+                "status": "success",
+                "type": "response",
+                "result": {"closed": true}
+            }))
+            .unwrap();
+            pending.response.clone().send(Ok(res)).await.unwrap();
+        };
+        let (closed, _) = futures::join!(ws.path_find_close(), respond);
+        assert_eq!(closed.unwrap().closed, Some(true));
+        assert!(paths.next().await.is_none());
+        // The closed request is forgotten, so it isn't replayed after a reconnect.
+        let subs = ws.subscriptions.lock().unwrap();
+        assert!(subs.iter().all(|sub| sub.request.command == "subscribe"));
+    }
+
+    #[tokio::test]
+    async fn http_request_times_out() {
+        // Accepts connections but never responds.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+        let http = HTTP::builder()
+            .with_host(&addr.ip().to_string(), addr.port(), false)
+            .unwrap()
+            .with_timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        let res = http
+            .send_request::<Value, Value>("server_info", json!({}))
             .await;
+        assert!(matches!(res, Err(TransportError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn http_request_retried_until_available() {
+        // Responds 503 Service Unavailable to the first two requests.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let attempts = Arc::new(AtomicU64::new(0));
+        let server_attempts = attempts.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request).await;
+                let response = if server_attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    "HTTP/1.1 503 Service Unavailable\r\nConnection: close\r\nContent-Length: 0\r\n\r\n".to_owned()
+                } else {
+                    let body = r#"{"result":{"status":"success","ledger_index":5}}"#;
+                    format!(
+                        "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
         });
+        let http = HTTP::builder()
+            .with_host(&addr.ip().to_string(), addr.port(), false)
+            .unwrap()
+            .with_retries(2)
+            .with_retry_backoff(Duration::from_millis(1))
+            .build()
+            .unwrap();
+        let res = http
+            .send_request::<Value, Value>("ledger_current", json!({}))
+            .await
+            .unwrap();
+        assert_eq!(res["ledger_index"], json!(5));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn websocket_reconnects_and_replays_subscriptions() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Sends one ledger to each connection's subscription, then drops the connection.
         tokio::spawn(async move {
-            receiver
-                .map(|req| match req {
-                    Outbound::PendingRequest(req) => {
-                        Message::Text(serde_json::to_string(&req.request).unwrap())
-                    }
-                    Outbound::Subscription(req) => {
-                        Message::Text(serde_json::to_string(&req.request).unwrap())
-                    }
-                })
-                .map(Ok)
-                .forward(write)
-                .await
-                .unwrap();
+            for ledger_index in 1..=2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let request = match ws.next().await {
+                    Some(Ok(Message::Text(request))) => request,
+                    message => panic!("expected a subscribe request, got {:?}", message),
+                };
+                let request: Value = serde_json::from_str(&request).unwrap();
+                assert_eq!(request["command"], "subscribe");
+                assert_eq!(request["streams"], json!(["ledger"]));
+                let event = json!({
+                    "type": "ledgerClosed",
+                    "ledger_hash": "",
+                    "ledger_index": ledger_index
+                });
+                ws.send(Message::Text(event.to_string())).await.unwrap();
+            }
         });
-        Ok(ws)
+        let ws = WebSocket::builder()
+            .with_host(&addr.ip().to_string(), addr.port(), false)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        let mut events = ws
+            .subscribe(SubscribeRequest::streams(vec!["ledger".to_owned()]))
+            .await
+            .unwrap();
+        for ledger_index in 1..=2 {
+            match events.next().await {
+                Some(Ok(SubscriptionEvent::LedgerClosed(ledger))) => {
+                    assert_eq!(ledger.ledger_index, ledger_index)
+                }
+                event => panic!("expected a closed ledger, got {:?}", event),
+            }
+        }
     }
 }
@@ -1,21 +1,35 @@
-use super::{Address, CurrencyAmount, LedgerInfo, PaginationInfo, SignerList, AccountRoot, LedgerEntry};
+use super::{Address, ClassicAddress, CurrencyAmount, LedgerInfo, PaginationInfo, SignerList, AccountRoot, LedgerEntry, BigInt, Hash256};
+use crate::transaction::types::Transaction;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use serde_with::skip_serializing_none;
 
 /// Used to make account_channels requests.
 #[skip_serializing_none]
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountChannelsRequest {
     /// A unique identifier for the account, most commonly the account's Address.
-    pub account: Address,
+    pub account: ClassicAddress,
     /// (Optional) The unique identifier of an account, typically the account's Address. If provided, filter results to payment channels whose destination is this account.
-    pub destination_account: Option<Address>,
+    pub destination_account: Option<ClassicAddress>,
     #[serde(flatten)]
     pub ledger_info: LedgerInfo,
     #[serde(flatten)]
     pub pagination: PaginationInfo,
 }
 
+impl AccountChannelsRequest {
+    /// Creates a request for the channels of `account`, leaving the optional fields unset.
+    pub fn new(account: ClassicAddress) -> Self {
+        Self {
+            account,
+            destination_account: None,
+            ledger_info: LedgerInfo::default(),
+            pagination: PaginationInfo::default(),
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountChannelsResponse {
@@ -29,6 +43,13 @@ pub struct AccountChannelsResponse {
     pub pagination: PaginationInfo,
 }
 
+impl AccountChannelsResponse {
+    /// Returns true if this is the last page of payment channels.
+    pub fn is_done(&self) -> bool {
+        self.pagination.is_done()
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountChannel {
@@ -39,7 +60,7 @@ pub struct AccountChannel {
     /// The total amount of XRP, in drops, paid out from this channel, as of the ledger version used. (You can calculate the amount of XRP left in the channel by subtracting balance from amount.)
     pub balance: CurrencyAmount,
     /// A unique ID for this channel, as a 64-character hexadecimal string. This is also the ID of the channel object in the ledger's state data.
-    pub channel_id: String,
+    pub channel_id: Hash256,
     /// The destination account of the channel, as an Address. Only this account can receive the XRP in the channel while it is open.
     pub destination_account: Address,
     /// The number of seconds the payment channel must stay open after the owner of the channel requests to close it.
@@ -60,10 +81,10 @@ pub struct AccountChannel {
 
 /// Used to make account_currencies requests.
 #[skip_serializing_none]
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountCurrenciesRequest {
     /// A unique identifier for the account, most commonly the account's Address.
-    pub account: Address,
+    pub account: ClassicAddress,
     #[serde(flatten)]
     pub ledger_info: LedgerInfo,
     #[serde(flatten)]
@@ -72,6 +93,18 @@ pub struct AccountCurrenciesRequest {
     pub strict: Option<bool>,
 }
 
+impl AccountCurrenciesRequest {
+    /// Creates a request for the currencies of `account`, leaving the optional fields unset.
+    pub fn new(account: ClassicAddress) -> Self {
+        Self {
+            account,
+            ledger_info: LedgerInfo::default(),
+            pagination: PaginationInfo::default(),
+            strict: None,
+        }
+    }
+}
+
 /// The response type for an account_currencies request.
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
@@ -86,10 +119,10 @@ pub struct AccountCurrenciesResponse {
 
 /// Used to make account_info requests.
 #[skip_serializing_none]
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountInfoRequest {
     /// A unique identifier for the account, most commonly the account's Address.
-    pub account: Address,
+    pub account: ClassicAddress,
     /// (Optional) If true, then the account field only accepts a public key or XRP Ledger address. Otherwise, account can be a secret or passphrase (not recommended). The default is false.
     pub strict: Option<bool>,
     #[serde(flatten)]
@@ -100,6 +133,19 @@ pub struct AccountInfoRequest {
     pub signer_lists: Option<bool>,
 }
 
+impl AccountInfoRequest {
+    /// Creates a request for the info of `account`, leaving the optional fields unset.
+    pub fn new(account: ClassicAddress) -> Self {
+        Self {
+            account,
+            strict: None,
+            ledger_info: LedgerInfo::default(),
+            queue: None,
+            signer_lists: None,
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountInfoResponse {
@@ -147,18 +193,38 @@ pub struct AccountQueuedTransaction {
 
 /// Used to make account_line requests.
 #[skip_serializing_none]
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountLinesRequest {
     /// A unique identifier for the account, most commonly the account's Address.
-    pub account: Address,
+    pub account: ClassicAddress,
     #[serde(flatten)]
     pub ledger_info: LedgerInfo,
     /// (Optional) The Address of a second account. If provided, show only lines of trust connecting the two accounts.
-    pub peer: Option<Address>,
+    pub peer: Option<ClassicAddress>,
     #[serde(flatten)]
     pub pagination: Option<PaginationInfo>,
 }
 
+impl AccountLinesRequest {
+    /// Creates a request for the trust lines of `account`, leaving the optional fields unset.
+    pub fn new(account: ClassicAddress) -> Self {
+        Self {
+            account,
+            ledger_info: LedgerInfo::default(),
+            peer: None,
+            pagination: None,
+        }
+    }
+    /// Returns the request for the page after the provided response, or `None` if the response
+    /// was the last page. The `peer` filter is kept so the next page stays scoped to it.
+    pub fn next_page(&self, response: &AccountLinesResponse) -> Option<Self> {
+        let marker = response.pagination.as_ref()?.marker.clone()?;
+        let mut req = self.clone();
+        req.pagination.get_or_insert_with(Default::default).marker = Some(marker);
+        Some(req)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountLinesResponse {
@@ -172,6 +238,16 @@ pub struct AccountLinesResponse {
     pub pagination: Option<PaginationInfo>,
 }
 
+impl AccountLinesResponse {
+    /// Returns true if this is the last page of trust lines.
+    pub fn is_done(&self) -> bool {
+        self.pagination
+            .as_ref()
+            .map(|pagination| pagination.is_done())
+            .unwrap_or(true)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountTrustLine {
@@ -204,15 +280,27 @@ pub struct AccountTrustLine {
 }
 
 #[skip_serializing_none]
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountOfferRequest {
-    pub account: Address,
+    pub account: ClassicAddress,
     #[serde(flatten)]
     pub ledger_info: LedgerInfo,
     pub limit: Option<i64>,
     pub strict: Option<bool>,
 }
 
+impl AccountOfferRequest {
+    /// Creates a request for the offers of `account`, leaving the optional fields unset.
+    pub fn new(account: ClassicAddress) -> Self {
+        Self {
+            account,
+            ledger_info: LedgerInfo::default(),
+            limit: None,
+            strict: None,
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountOfferResponse {
@@ -233,10 +321,10 @@ pub struct AccountOffer {
 
 /// Used to make account_objects requests.
 #[skip_serializing_none]
-#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AccountObjectsRequest {
     /// A unique identifier for the account, most commonly the account's address.
-    pub account: Address,
+    pub account: ClassicAddress,
     /// (Optional) If included, filter results to include only this type of ledger object. The valid types are: check , deposit_preauth, escrow, offer, payment_channel, signer_list, ticket , and state (trust line).
     pub r#type: Option<AccountObjectType>,
     /// (Optional) If true, the response only includes objects that would block this account from being deleted. The default is false. New in: rippled 1.4.0
@@ -247,6 +335,19 @@ pub struct AccountObjectsRequest {
     pub pagination: PaginationInfo,
 }
 
+impl AccountObjectsRequest {
+    /// Creates a request for the objects of `account`, leaving the optional fields unset.
+    pub fn new(account: ClassicAddress) -> Self {
+        Self {
+            account,
+            r#type: None,
+            deletion_blockers_only: None,
+            ledger_info: LedgerInfo::default(),
+            pagination: PaginationInfo::default(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum AccountObjectType {
@@ -276,12 +377,180 @@ pub struct AccountObjectsResponse {
     pub account_objects: Option<Vec<LedgerEntry>>,
 }
 
+/// A summary of an account's XRP balance and the portion of it that is available to spend.
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct AccountSummary {
+    /// The account's current XRP balance in drops.
+    pub balance: BigInt,
+    /// The XRP, in drops, the account must hold in reserve for itself and the objects it owns.
+    pub reserve: BigInt,
+    /// The XRP, in drops, the account can spend without dipping into its reserve.
+    pub spendable: BigInt,
+    /// The number of objects this account owns in the ledger.
+    pub owner_count: u32,
+    /// The sequence number of the next valid transaction for this account.
+    pub sequence: u32,
+}
 
 /// Used to make account_tx requests.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct AccountTXRequest {
+    /// A unique identifier for the account, most commonly the account's address.
+    pub account: ClassicAddress,
+    /// (Optional) Use to specify the earliest ledger to include transactions from. A value of -1 instructs the server to use the earliest validated ledger version available.
+    pub ledger_index_min: Option<i64>,
+    /// (Optional) Use to specify the most recent ledger to include transactions from. A value of -1 instructs the server to use the most recent validated ledger version available.
+    pub ledger_index_max: Option<i64>,
+    /// (Optional) Defaults to false. If set to true, returns transactions as hex strings instead of JSON.
+    pub binary: Option<bool>,
+    /// (Optional) Defaults to false. If set to true, returns values indexed with the oldest ledger first. Otherwise, the results are indexed with the newest ledger first. (Each page of results may not be internally ordered, but the pages are overall ordered.)
+    pub forward: Option<bool>,
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+    #[serde(flatten)]
+    pub pagination: PaginationInfo,
+}
+
+impl AccountTXRequest {
+    /// Creates a request for the transactions of `account`, leaving the optional fields unset.
+    pub fn new(account: ClassicAddress) -> Self {
+        Self {
+            account,
+            ledger_index_min: None,
+            ledger_index_max: None,
+            binary: None,
+            forward: None,
+            ledger_info: LedgerInfo::default(),
+            pagination: PaginationInfo::default(),
+        }
+    }
+    /// Returns the request for the page after the provided response, or `None` if the response
+    /// was the last page.
+    pub fn next_page(&self, response: &AccountTXResponse) -> Option<Self> {
+        let marker = response.pagination.marker.clone()?;
+        let mut req = self.clone();
+        req.pagination.marker = Some(marker);
+        Some(req)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
-pub struct AccountTXRequest {}
+pub struct AccountTXResponse {
+    /// Unique Address identifying the related account.
+    pub account: Address,
+    /// The ledger index of the earliest ledger actually searched for transactions.
+    pub ledger_index_min: Option<i64>,
+    /// The ledger index of the most recent ledger actually searched for transactions.
+    pub ledger_index_max: Option<i64>,
+    /// Array of transactions matching the request's criteria, as explained below.
+    pub transactions: Vec<AccountTx>,
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+    #[serde(flatten)]
+    pub pagination: PaginationInfo,
+}
+
+impl AccountTXResponse {
+    /// Returns true if this is the last page of transactions.
+    pub fn is_done(&self) -> bool {
+        self.pagination.is_done()
+    }
+}
 
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
-pub struct AccountTXResponse {}
+pub struct AccountTx {
+    /// The ledger index of the ledger version that included this transaction.
+    pub ledger_index: Option<u32>,
+    /// (JSON mode) JSON object defining the transaction.
+    pub tx: Option<Transaction>,
+    /// (Binary mode) A unique hashed String representing the transaction.
+    pub tx_blob: Option<String>,
+    /// If binary is true, then this is a hex string of the transaction results metadata. Otherwise, the transaction results metadata is included in JSON format.
+    pub meta: Option<Value>,
+    /// Whether or not the transaction is included in a validated ledger. Any transaction not yet in a validated ledger is subject to change.
+    pub validated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountLinesRequest, AccountLinesResponse, AccountTXRequest, AccountTXResponse};
+    use crate::types::PaginationInfo;
+    use serde_json::json;
+
+    #[test]
+    fn account_tx_marker_round_trip() {
+        let res: AccountTXResponse = serde_json::from_value(json!({
+            "account": "rLNaPoKeeBjZe2qs6x52yVPZpZ8td4dc6w",
+            "ledger_index_max": 57954540,
+            "ledger_index_min": 32570,
+            "limit": 1,
+            "marker": {"ledger": 57954540, "seq": 3},
+            "transactions": [{
+                "ledger_index": 57954540,
+                "meta": {"TransactionResult": "tesSUCCESS"},
+                "tx": {
+                    "Account": "rLNaPoKeeBjZe2qs6x52yVPZpZ8td4dc6w",
+                    "Amount": "1000",
+                    "Destination": "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw",
+                    "Fee": "12",
+                    "LastLedgerSequence": 57954550,
+                    "Sequence": 5,
+                    "SigningPubKey": "",
+                    "TransactionType": "Payment",
+                    "hash": "EC3E0B2B9D9F4C1D8A0F9CE9A8F1A5B2A48E0B3E3C07C29D1F0AB3D77E4D2F10"
+                },
+                "validated": true
+            }],
+            "validated": true
+        }))
+        .unwrap();
+        assert!(!res.is_done());
+        assert_eq!(res.transactions[0].tx.as_ref().unwrap().sequence, 5);
+        let req = AccountTXRequest::new("rLNaPoKeeBjZe2qs6x52yVPZpZ8td4dc6w".parse().unwrap());
+        let next = serde_json::to_value(req.next_page(&res).unwrap()).unwrap();
+        assert_eq!(next["marker"], json!({"ledger": 57954540, "seq": 3}));
+    }
+
+    #[test]
+    fn account_lines_peer_and_marker() {
+        let req = AccountLinesRequest {
+            peer: Some("rMwjYedjc7qqtKYVLiAccJSmCwih4LnE2q".parse().unwrap()),
+            pagination: Some(PaginationInfo {
+                limit: Some(10),
+                marker: None,
+            }),
+            ..AccountLinesRequest::new("rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn".parse().unwrap())
+        };
+        let res: AccountLinesResponse = serde_json::from_value(json!({
+            "account": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn",
+            "lines": [],
+            "limit": 10,
+            "marker": "BF9E6B1BE9B5A6B0A1D1D7F0E1F1C1D1E1F1A1B1C1D1E1F1A1B1C1D1E1F1A1B1,0",
+            "validated": true
+        }))
+        .unwrap();
+        assert!(!res.is_done());
+        let next = req.next_page(&res).unwrap();
+        let value = serde_json::to_value(&next).unwrap();
+        assert_eq!(value["peer"], "rMwjYedjc7qqtKYVLiAccJSmCwih4LnE2q");
+        assert_eq!(value["limit"], 10);
+        assert_eq!(
+            value["marker"],
+            "BF9E6B1BE9B5A6B0A1D1D7F0E1F1C1D1E1F1A1B1C1D1E1F1A1B1C1D1E1F1A1B1,0"
+        );
+        let parsed: AccountLinesRequest = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, next);
+
+        let last: AccountLinesResponse = serde_json::from_value(json!({
+            "account": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn",
+            "validated": true
+        }))
+        .unwrap();
+        assert!(last.is_done());
+        assert!(req.next_page(&last).is_none());
+        assert!(last.lines.unwrap_or_default().is_empty());
+    }
+}
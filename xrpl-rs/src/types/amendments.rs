@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_with::skip_serializing_none;
+
+use super::H256;
+
+/// Used to make feature requests.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct FeatureRequest {
+    /// (Optional) The unique ID of an amendment, as hexadecimal; or the short name of the amendment. If provided, limits the response to one amendment. Otherwise, the response lists all amendments.
+    pub feature: Option<String>,
+}
+
+#[derive(Default, Debug, Serialize, Eq, PartialEq, Clone)]
+pub struct FeatureResponse {
+    /// Map of amendment ids to the status of the amendment.
+    pub features: HashMap<H256, Feature>,
+}
+
+impl FeatureResponse {
+    /// Returns the amendment with the provided id or short name, if the server knows of it.
+    pub fn get(&self, feature: &str) -> Option<&Feature> {
+        self.features.get(feature).or_else(|| {
+            self.features
+                .values()
+                .find(|f| f.name.as_deref() == Some(feature))
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for FeatureResponse {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        // All amendments are listed under `features`, while a single requested amendment is keyed
+        // by its id at the top level of the response.
+        let mut result = HashMap::<String, Value>::deserialize(deserializer)?;
+        let features = match result.remove("features") {
+            Some(features) => serde_json::from_value(features).map_err(serde::de::Error::custom)?,
+            None => result
+                .into_iter()
+                .filter_map(|(id, v)| Some((id, serde_json::from_value(v).ok()?)))
+                .collect(),
+        };
+        Ok(Self { features })
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct Feature {
+    /// The short name of the amendment, if the server knows it.
+    pub name: Option<String>,
+    /// Whether this amendment is currently enabled in the latest ledger.
+    pub enabled: bool,
+    /// Whether the server knows how to apply this amendment.
+    pub supported: bool,
+    /// (May be omitted) Whether the server has been instructed to vote against this amendment, or `"Obsolete"` for amendments that are no longer voted on.
+    pub vetoed: Option<Value>,
+}
+
+/// The Amendments ledger object, which tracks the status of amendments in the ledger.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Amendments {
+    /// (Optional) Array of 256-bit amendment IDs for all currently enabled amendments. If omitted, there are no enabled amendments.
+    pub amendments: Option<Vec<H256>>,
+    /// (Optional) Array of objects describing the status of amendments that have majority support but are not yet enabled. If omitted, there are no pending amendments with majority support.
+    pub majorities: Option<Vec<MajorityWrapper>>,
+    /// A bit-map of boolean flags. No flags are defined for the Amendments object type, so this value is always 0.
+    pub flags: u32,
+}
+
+impl Amendments {
+    /// Returns whether the amendment with the provided id is enabled.
+    pub fn is_enabled(&self, amendment: &str) -> bool {
+        self.amendments
+            .as_ref()
+            .map(|a| a.iter().any(|id| id.eq_ignore_ascii_case(amendment)))
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct MajorityWrapper {
+    pub majority: Majority,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Majority {
+    /// The Amendment ID of the pending amendment.
+    pub amendment: H256,
+    /// The close_time field of the ledger version where this amendment most recently gained a majority.
+    pub close_time: u64,
+}
@@ -0,0 +1,210 @@
+use super::{Address, ClassicAddress, CurrencyAmount, Hash256, LedgerInfo};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Used to make book_offers requests.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct BookOffersRequest {
+    /// The asset the account taking the Offer would receive.
+    pub taker_gets: BookCurrency,
+    /// The asset the account taking the Offer would pay.
+    pub taker_pays: BookCurrency,
+    /// (Optional) The address of an account to use as a perspective. Unfunded offers placed by this account are always included in the response.
+    pub taker: Option<ClassicAddress>,
+    /// (Optional) The maximum number of Offers to return. The response may include fewer results.
+    pub limit: Option<u32>,
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+}
+
+/// A currency, and its issuer for non-XRP currencies, identifying one side of an order book.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct BookCurrency {
+    /// A Currency Code, or "XRP".
+    pub currency: String,
+    /// (Omitted for XRP) The address of the currency's issuer.
+    pub issuer: Option<Address>,
+}
+
+impl BookCurrency {
+    /// Identifies XRP, which is serialized without an issuer.
+    pub fn xrp() -> Self {
+        Self {
+            currency: "XRP".to_owned(),
+            issuer: None,
+        }
+    }
+    /// Identifies a currency issued by the provided address.
+    pub fn issued(currency: &str, issuer: &Address) -> Self {
+        Self {
+            currency: currency.to_owned(),
+            issuer: Some(issuer.to_owned()),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct BookOffersResponse {
+    /// Array of offer objects, each of which has the fields of an Offer object.
+    pub offers: Vec<BookOffer>,
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct BookOffer {
+    /// The address of the account that placed this Offer.
+    pub account: Address,
+    /// A bit-map of boolean flags enabled for this Offer.
+    pub flags: u32,
+    /// The Sequence value of the OfferCreate transaction that created this Offer object.
+    pub sequence: u32,
+    /// The remaining amount and type of currency being provided by the Offer creator.
+    pub taker_gets: CurrencyAmount,
+    /// The remaining amount and type of currency requested by the Offer creator.
+    pub taker_pays: CurrencyAmount,
+    /// The ID of the Offer Directory that links to this Offer.
+    pub book_directory: Option<Hash256>,
+    /// A hint indicating which page of the Offer Directory links to this object, in case the directory consists of multiple pages.
+    pub book_node: Option<String>,
+    /// A hint indicating which page of the Owner Directory links to this object, in case the directory consists of multiple pages.
+    pub owner_node: Option<String>,
+    /// (Optional) Indicates the time after which this Offer is considered unfunded.
+    pub expiration: Option<u32>,
+    /// The identifying hash of the transaction that most recently modified this object.
+    #[serde(rename = "PreviousTxnID")]
+    pub previous_txn_id: Option<Hash256>,
+    /// The index of the ledger that contains the transaction that most recently modified this object.
+    pub previous_txn_lgr_seq: Option<u32>,
+    /// Amount of the TakerGets currency the side placing the offer has available to be traded. (XRP is represented as drops; any other currency is represented as a decimal value.) If a trader has multiple offers in the same book, only the highest-ranked offer includes this field.
+    #[serde(rename = "owner_funds")]
+    pub owner_funds: Option<String>,
+    /// (Only included in partially-funded offers) The maximum amount of currency that the taker can get, given the funding status of the offer.
+    #[serde(rename = "taker_gets_funded")]
+    pub taker_gets_funded: Option<CurrencyAmount>,
+    /// (Only included in partially-funded offers) The maximum amount of currency that the taker would pay, given the funding status of the offer.
+    #[serde(rename = "taker_pays_funded")]
+    pub taker_pays_funded: Option<CurrencyAmount>,
+    /// The exchange rate, as the ratio taker_pays divided by taker_gets. For fairness, offers that have the same quality are automatically taken first-in, first-out.
+    #[serde(rename = "quality")]
+    pub quality: Option<String>,
+}
+
+impl BookOffer {
+    /// Returns whether the offer is backed by enough of the owner's balance to fill its nominal
+    /// amounts. Takers should use `taker_gets_funded` and `taker_pays_funded` for partially-funded
+    /// offers, otherwise crossing the offer may fail with `tecUNFUNDED_OFFER`.
+    pub fn is_fully_funded(&self) -> bool {
+        match &self.taker_gets_funded {
+            None => true,
+            Some(funded) => funded == &self.taker_gets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BookCurrency, BookOffersRequest, BookOffersResponse};
+    use serde_json::json;
+
+    #[test]
+    fn book_currency_serialization() {
+        let issuer = "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned();
+        let req = BookOffersRequest {
+            taker_gets: BookCurrency::xrp(),
+            taker_pays: BookCurrency::issued("USD", &issuer),
+            ..Default::default()
+        };
+        let value = serde_json::to_value(&req).unwrap();
+        assert_eq!(value["taker_gets"], json!({"currency": "XRP"}));
+        assert_eq!(
+            value["taker_pays"],
+            json!({"currency": "USD", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B"})
+        );
+    }
+
+    #[test]
+    fn book_offer_funding() {
+        let res: BookOffersResponse = serde_json::from_value(json!({
+            "ledger_current_index": 7035305,
+            "offers": [
+                {
+                    "Account": "rM3X3QSr8icjTGpaF52dozhbT2BZSXJQYM",
+                    "BookDirectory": "7E5F614417C2D0A7CEFEB73C4AA773ED5B078DE2B5771F6D55055E4C405218EB",
+                    "BookNode": "0000000000000000",
+                    "Flags": 0,
+                    "LedgerEntryType": "Offer",
+                    "OwnerNode": "0000000000000AE0",
+                    "PreviousTxnID": "6956221794397C25A53647182E5C78A439766D600724074C99D78982E37599F1",
+                    "PreviousTxnLgrSeq": 7022646,
+                    "Sequence": 264542,
+                    "TakerGets": {
+                        "currency": "EUR",
+                        "issuer": "rhub8VRN55s94qWKDv6jmDy1pUykJzF3wq",
+                        "value": "17.90363633316433"
+                    },
+                    "TakerPays": {
+                        "currency": "USD",
+                        "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+                        "value": "27.05340557506234"
+                    },
+                    "index": "96A9104BF3137131FF8310B9174F3B37170E2144C813CA2A1695DF2C5677E811",
+                    "quality": "1.511056473200875"
+                },
+                {
+                    "Account": "rDVBvAQScXrGRGnzrxRrcJPeNLeLeUTAqE",
+                    "BookDirectory": "7E5F614417C2D0A7CEFEB73C4AA773ED5B078DE2B5771F6D5505DCAA8FE12000",
+                    "BookNode": "0000000000000000",
+                    "Flags": 131072,
+                    "LedgerEntryType": "Offer",
+                    "OwnerNode": "0000000000000001",
+                    "PreviousTxnID": "8D65B7B2FC8CF8B5F8A0F3C0B6A5F7C8B0D0E5A9F0B7E4A3E6F1C2D3B4A59687",
+                    "PreviousTxnLgrSeq": 7026180,
+                    "Sequence": 1035,
+                    "TakerGets": {
+                        "currency": "EUR",
+                        "issuer": "rhub8VRN55s94qWKDv6jmDy1pUykJzF3wq",
+                        "value": "10"
+                    },
+                    "TakerPays": {
+                        "currency": "USD",
+                        "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+                        "value": "16.5"
+                    },
+                    "index": "1B8A4A8E3C1F5D2A6B7C8D9E0F1A2B3C4D5E6F708192A3B4C5D6E7F8091A2B3C",
+                    "owner_funds": "4",
+                    "quality": "1.65",
+                    "taker_gets_funded": {
+                        "currency": "EUR",
+                        "issuer": "rhub8VRN55s94qWKDv6jmDy1pUykJzF3wq",
+                        "value": "4"
+                    },
+                    "taker_pays_funded": {
+                        "currency": "USD",
+                        "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+                        "value": "6.6"
+                    }
+                }
+            ],
+            "validated": false
+        }))
+        .unwrap();
+        assert!(res.offers[0].is_fully_funded());
+        // The owner only holds 4 of the 10 EUR the offer nominally provides.
+        let partial = &res.offers[1];
+        assert!(!partial.is_fully_funded());
+        assert_eq!(
+            partial
+                .taker_pays_funded
+                .as_ref()
+                .unwrap()
+                .to_display_string(),
+            "6.6 USD (rvYAf...)"
+        );
+    }
+}
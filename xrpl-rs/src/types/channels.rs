@@ -1,4 +1,4 @@
-use super::{Address, CurrencyAmount, LedgerInfo, PaginationInfo, SignerList, AccountRoot, LedgerEntry, BigInt};
+use super::{Address, CurrencyAmount, LedgerInfo, PaginationInfo, SignerList, AccountRoot, LedgerEntry, BigInt, Hash256};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
@@ -6,7 +6,7 @@ use serde_with::skip_serializing_none;
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct ChannelVerifyRequest {
     pub amount: BigInt,
-    pub channel_id: String,
+    pub channel_id: Hash256,
     pub public_key: String,
     pub signature: String,
 }
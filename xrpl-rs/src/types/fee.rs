@@ -1,4 +1,4 @@
-use super::{Address, CurrencyAmount, LedgerInfo, PaginationInfo, SignerList, AccountRoot, LedgerEntry};
+use super::{Address, CurrencyAmount, LedgerInfo, PaginationInfo, SignerList, AccountRoot, LedgerEntry, Integer, BigInt};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
@@ -10,13 +10,55 @@ pub struct FeeRequest {}
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct FeeResponse {
+    /// Number of transactions currently queued for the next ledger.
+    pub current_queue_size: Option<Integer>,
+    /// The maximum number of transactions that the transaction queue can currently hold.
+    pub max_queue_size: Option<Integer>,
+    /// The approximate number of transactions expected to be included in the current ledger. This is based on the number of transactions in the previous ledger.
+    pub expected_ledger_size: Option<Integer>,
+    /// Number of transactions provisionally included in the in-progress ledger.
+    pub current_ledger_size: Option<Integer>,
+    /// The ledger index of the current open ledger these stats describe.
+    pub ledger_current_index: Option<u32>,
     /// Various information about the transaction cost (the Fee field of a transaction), in drops of XRP.
     pub drops: FeeResponseDrops,
+    /// (May be omitted) Various information about the transaction cost, in fee levels. The ratio in fee levels applies to any transaction relative to the minimum cost of that particular transaction.
+    pub levels: Option<FeeResponseLevels>,
+}
+
+impl FeeResponse {
+    /// Returns how full the transaction queue is as a percentage, if the server reported the queue size.
+    pub fn queue_percentage(&self) -> Option<u32> {
+        match (&self.current_queue_size, &self.max_queue_size) {
+            (Some(current), Some(max)) if max.0 > 0 => {
+                Some((current.0.min(max.0) as u64 * 100 / max.0 as u64) as u32)
+            }
+            _ => None,
+        }
+    }
 }
 
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct FeeResponseDrops {
+    /// The transaction cost required for a reference transaction to be included in a ledger under minimum load, represented in drops of XRP.
+    pub base_fee: Option<CurrencyAmount>,
+    /// An approximation of the median transaction cost among transactions included in the previous validated ledger, represented in drops of XRP.
+    pub median_fee: Option<CurrencyAmount>,
+    /// The minimum transaction cost for a reference transaction to be queued for a later ledger, represented in drops of XRP. If greater than base_fee, the transaction queue is full.
+    pub minimum_fee: Option<CurrencyAmount>,
     /// The minimum transaction cost that a reference transaction must pay to be included in the current open ledger, represented in drops of XRP.
     pub open_ledger_fee: CurrencyAmount,
-}
\ No newline at end of file
+}
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct FeeResponseLevels {
+    /// The median transaction cost among transactions in the previous validated ledger, represented in fee levels.
+    pub median_level: BigInt,
+    /// The minimum transaction cost required to be queued for a future ledger, represented in fee levels.
+    pub minimum_level: BigInt,
+    /// The minimum transaction cost required to be included in the current open ledger, represented in fee levels.
+    pub open_ledger_level: BigInt,
+    /// The equivalent of the minimum transaction cost, represented in fee levels.
+    pub reference_level: BigInt,
+}
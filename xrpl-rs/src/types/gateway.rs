@@ -0,0 +1,114 @@
+use super::{Address, ClassicAddress, IssuedCurrencyAmount, LedgerInfo};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
+
+/// Used to make gateway_balances requests.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct GatewayBalancesRequest {
+    /// The Address to check. This should be the issuing address.
+    pub account: ClassicAddress,
+    /// (Optional) If true, only accept an address or public key for the account parameter. Defaults to false.
+    pub strict: Option<bool>,
+    /// (Optional) An operational address to exclude from the balances issued, or an array of such addresses.
+    pub hotwallet: Option<Vec<ClassicAddress>>,
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+}
+
+impl GatewayBalancesRequest {
+    /// Creates a request for the issued balances of `account`, leaving the optional fields unset.
+    pub fn new(account: ClassicAddress) -> Self {
+        Self {
+            account,
+            strict: None,
+            hotwallet: None,
+            ledger_info: LedgerInfo::default(),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct GatewayBalancesResponse {
+    /// The address of the account that issued the balances.
+    pub account: Address,
+    /// (Omitted if empty) Total amounts issued to addresses not excluded, as a map of currencies to the total value issued.
+    pub obligations: Option<HashMap<String, Decimal>>,
+    /// (Omitted if empty) Amounts issued to the hotwallet addresses from the request. The keys are addresses and the values are arrays of currency amounts they hold.
+    pub balances: Option<HashMap<Address, Vec<GatewayBalance>>>,
+    /// (Omitted if empty) Amounts issued to the hotwallet addresses from the request that are frozen, in the same format as balances.
+    pub frozen_balances: Option<HashMap<Address, Vec<GatewayBalance>>>,
+    /// (Omitted if empty) Total amounts held that are issued by others. In the recommended configuration, the issuing address should have none.
+    pub assets: Option<HashMap<Address, Vec<GatewayBalance>>>,
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+}
+
+impl GatewayBalancesResponse {
+    /// Returns the amounts issued by this account to the provided hotwallet address, as issued
+    /// currency amounts with this account as the issuer.
+    pub fn balances_for(&self, hotwallet: &Address) -> Vec<IssuedCurrencyAmount> {
+        self.balances
+            .as_ref()
+            .and_then(|balances| balances.get(hotwallet))
+            .map(|balances| {
+                balances
+                    .iter()
+                    .map(|balance| IssuedCurrencyAmount {
+                        value: balance.value,
+                        currency: balance.currency.clone(),
+                        issuer: self.account.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// A currency amount in a gateway_balances response. The issuer is implied by where the amount
+/// appears in the response.
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct GatewayBalance {
+    /// The currency code of the amount.
+    pub currency: String,
+    /// The amount held, as a decimal.
+    pub value: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GatewayBalancesResponse;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    #[test]
+    fn deserialize_gateway_balances() {
+        let res: GatewayBalancesResponse = serde_json::from_value(json!({
+            "account": "rMwjYedjc7qqtKYVLiAccJSmCwih4LnE2q",
+            "assets": {
+                "r9F6wk8HkXrgYWoJ7fsv4VrUBVoqDVtzkH": [{"currency": "BTC", "value": "5.44416651"}]
+            },
+            "balances": {
+                "rKm4uWpg9tfwbVSeATv4KxDe6mpE9yPkgJ": [{"currency": "EUR", "value": "29826.1965999999"}]
+            },
+            "obligations": {"BTC": "5908.324927635318", "EUR": "992471.7419793958"},
+            "ledger_current_index": 14483212,
+            "validated": false
+        }))
+        .unwrap();
+        assert_eq!(
+            res.obligations.as_ref().unwrap()["EUR"],
+            Decimal::from_str("992471.7419793958").unwrap()
+        );
+        let balances = res.balances_for(&"rKm4uWpg9tfwbVSeATv4KxDe6mpE9yPkgJ".to_owned());
+        assert_eq!(balances[0].issuer, "rMwjYedjc7qqtKYVLiAccJSmCwih4LnE2q");
+        assert_eq!(
+            balances[0].value,
+            Decimal::from_str("29826.1965999999").unwrap()
+        );
+    }
+}
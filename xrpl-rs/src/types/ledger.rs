@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use serde_with::skip_serializing_none;
 
-use super::LedgerInfo;
+use super::{Address, Hash256, LedgerEntry, LedgerInfo, PaginationInfo};
+use crate::utils::ripple_time_to_unix;
 
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -10,17 +12,55 @@ pub struct LedgerRequest {
     pub ledger_hash: Option<String>,
     /// (Optional) The ledger index of the ledger to use, or a shortcut string to choose a ledger automatically. (See Specifying Ledgers)
     pub ledger_index: LedgerRequestIndex,
+    /// (Optional) If true, return information on transactions in the specified ledger version. Defaults to false. Ignored if you did not specify a ledger version.
+    pub transactions: Option<bool>,
+    /// (Optional) Provide full JSON-formatted information for transaction/account information instead of only hashes. Defaults to false. Ignored unless you request transactions, accounts, or both.
+    pub expand: Option<bool>,
+    /// (Optional) If true, and transactions and expand are both also true, return transaction information in binary format (hexadecimal string) instead of JSON format.
+    pub binary: Option<bool>,
+    /// (Optional) If true, return ledger state information. Admin required. Defaults to false.
+    pub accounts: Option<bool>,
+    /// (Optional) If true, include owner_funds field in the metadata of OfferCreate transactions in the response. Defaults to false. Ignored unless transactions are included and expand is true.
+    pub owner_funds: Option<bool>,
+    /// (Optional) If true, return full information on the entire ledger. Ignored if you did not specify a ledger version. Defaults to false. (Equivalent to enabling transactions, accounts, and expand.) Admin required.
+    pub full: Option<bool>,
 }
 
-#[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
-#[serde(untagged)]
+/// The ledger to request, either the most recent validated ledger or a ledger by its index.
+#[derive(Debug, Eq, PartialEq)]
 pub enum LedgerRequestIndex {
-    #[serde(rename="validated")]
     Validated,
     Index(u32),
 }
 
+impl Serialize for LedgerRequestIndex {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Validated => serializer.serialize_str("validated"),
+            Self::Index(index) => serializer.serialize_u32(*index),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for LedgerRequestIndex {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        match Value::deserialize(deserializer)? {
+            Value::String(s) if s == "validated" => Ok(Self::Validated),
+            v => v
+                .as_u64()
+                .and_then(|index| index.try_into().ok())
+                .map(Self::Index)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid ledger index: {}", v))),
+        }
+    }
+}
+
 impl Default for LedgerRequestIndex {
     fn default() -> Self {
         Self::Validated
@@ -39,4 +79,352 @@ pub struct LedgerResponse {
 pub struct Ledger {
     #[serde(flatten)]
     pub ledger_info: LedgerInfo,
-}
\ No newline at end of file
+    /// (May be omitted) The SHA-512Half of this ledger's state tree information.
+    pub account_hash: Option<String>,
+    /// (May be omitted) The time this ledger was closed, in seconds since the Ripple Epoch.
+    pub close_time: Option<u64>,
+    /// (May be omitted) The time this ledger was closed, in human-readable format. Always uses the UTC time zone.
+    pub close_time_human: Option<String>,
+    /// (May be omitted) Ledger close times are rounded to within this many seconds.
+    pub close_time_resolution: Option<u32>,
+    /// (May be omitted) Whether or not this ledger has been closed.
+    pub closed: Option<bool>,
+    /// (May be omitted) Unique identifying hash of the ledger that came immediately before this one.
+    pub parent_hash: Option<String>,
+    /// (May be omitted) Total number of XRP drops in the network, as a quoted integer.
+    pub total_coins: Option<String>,
+    /// (May be omitted) Hash of the transaction information included in this ledger, as hex.
+    pub transaction_hash: Option<String>,
+    /// (Omitted unless requested) Transactions applied in this ledger version. By default, members are the transactions' identifying Hash strings. If the request specified expand as true, members are full representations of the transactions instead, in either JSON or binary depending on whether the request specified binary as true.
+    pub transactions: Option<Vec<Value>>,
+}
+
+impl Ledger {
+    /// Returns the time this ledger was closed, in seconds since the Unix epoch.
+    pub fn close_time_unix(&self) -> Option<i64> {
+        self.close_time.map(ripple_time_to_unix)
+    }
+    /// Returns the number of seconds between the close of `previous` and the close of this
+    /// ledger. Close times are rounded to the ledger's `close_time_resolution`, so intervals
+    /// between consecutive ledgers are approximate.
+    pub fn close_interval(&self, previous: &Ledger) -> Option<i64> {
+        Some(self.close_time? as i64 - previous.close_time? as i64)
+    }
+}
+
+/// A transaction and its metadata in binary form, as returned in a ledger's transactions when the
+/// ledger is requested with `transactions`, `expand` and `binary` set.
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct BinaryTransaction {
+    /// The transaction, serialized in the XRPL binary format as hex.
+    pub tx_blob: String,
+    /// The transaction metadata, serialized in the XRPL binary format as hex.
+    pub meta: String,
+}
+
+/// Used to make ledger_data requests, which page through every object in a ledger's state.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct LedgerDataRequest {
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+    /// (Optional) If set to true, return ledger objects as hashed hex strings instead of JSON.
+    pub binary: Option<bool>,
+    /// (Optional) Filter results to a specific type of ledger entry, e.g. `"offer"`.
+    #[serde(rename = "type")]
+    pub r#type: Option<String>,
+    #[serde(flatten)]
+    pub pagination: PaginationInfo,
+}
+
+impl LedgerDataRequest {
+    /// Returns the request for the page after the provided response, or `None` if the response
+    /// was the last page. The ledger should be pinned with `ledger_info`, so that every page is
+    /// of the same ledger.
+    pub fn next_page(&self, response: &LedgerDataResponse) -> Option<Self> {
+        let marker = response.pagination.marker.clone()?;
+        let mut req = self.clone();
+        req.pagination.marker = Some(marker);
+        Some(req)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct LedgerDataResponse {
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+    /// Array of JSON objects containing data from the ledger's state tree, in either JSON or
+    /// binary form depending on the `binary` field of the request.
+    pub state: LedgerDataState,
+    #[serde(flatten)]
+    pub pagination: PaginationInfo,
+}
+
+/// The ledger objects returned by ledger_data, which are parsed unless the request set `binary`.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum LedgerDataState {
+    Binary(Vec<BinaryLedgerEntry>),
+    Json(Vec<LedgerEntry>),
+}
+
+/// A ledger object in binary form, as returned by ledger_data when the request set `binary`.
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct BinaryLedgerEntry {
+    /// The ledger object, serialized in the XRPL binary format as hex.
+    pub data: String,
+    /// The ID of the ledger object, as hex.
+    pub index: String,
+}
+
+/// Used to make ledger_entry requests, which return a single ledger object.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct LedgerEntryRequest {
+    /// The object to return. Only the selected selector is sent.
+    #[serde(flatten)]
+    pub selector: LedgerEntrySelector,
+    /// (Optional) If true, return the requested ledger object's contents as a hex string in the XRP Ledger's binary format. Otherwise, return data in JSON format. The default is false.
+    pub binary: Option<bool>,
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+}
+
+impl LedgerEntryRequest {
+    /// Creates a request for the object selected by `selector`. No ledger is specified, so the
+    /// server uses its default, normally the current in-progress ledger; set `ledger_info` to
+    /// select a specific ledger version.
+    pub fn new(selector: LedgerEntrySelector) -> Self {
+        Self {
+            selector,
+            binary: None,
+            ledger_info: LedgerInfo::default(),
+        }
+    }
+    /// Creates a request for a payment channel by its 64 character hex channel ID.
+    pub fn payment_channel(channel_id: &str) -> Result<Self, crate::Error> {
+        Ok(Self::new(LedgerEntrySelector::PaymentChannel(
+            channel_id.parse()?,
+        )))
+    }
+}
+
+/// The ways to select the object returned by ledger_entry. rippled accepts exactly one.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerEntrySelector {
+    /// Any ledger object, by its unique ID.
+    Index(Hash256),
+    /// An AccountRoot object, by the address of the account.
+    AccountRoot(Address),
+    /// A DirectoryNode object.
+    Directory(DirectorySelector),
+    /// An Offer object.
+    Offer(OfferSelector),
+    /// An Escrow object.
+    Escrow(EscrowSelector),
+    /// A PayChannel object, by its channel ID.
+    PaymentChannel(Hash256),
+    /// A Check object, by its ID.
+    Check(Hash256),
+    /// A DepositPreauth object.
+    DepositPreauth(DepositPreauthSelector),
+    /// A Ticket object.
+    Ticket(TicketSelector),
+}
+
+/// Selects a DirectoryNode by its ID, or a page of the directory owned by an account or with the
+/// provided root.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum DirectorySelector {
+    Index(Hash256),
+    Page {
+        /// (Optional) If provided, jumps to a later "page" of the DirectoryNode.
+        sub_index: Option<u64>,
+        /// (Optional) Unique index identifying the directory to retrieve, as a hex string.
+        dir_root: Option<Hash256>,
+        /// (Optional) Unique address of the account associated with this directory.
+        owner: Option<Address>,
+    },
+}
+
+/// Selects an Offer by its ID, or by the account that placed it and its sequence number.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum OfferSelector {
+    Index(Hash256),
+    Account { account: Address, seq: u32 },
+}
+
+/// Selects an Escrow by its ID, or by its owner and the sequence number of the EscrowCreate
+/// transaction that created it.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum EscrowSelector {
+    Index(Hash256),
+    Owner { owner: Address, seq: u32 },
+}
+
+/// Selects a DepositPreauth by its ID, or by the account that granted and the account that
+/// received the preauthorization.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum DepositPreauthSelector {
+    Index(Hash256),
+    Owner { owner: Address, authorized: Address },
+}
+
+/// Selects a Ticket by its ID, or by its owner and ticket sequence number.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum TicketSelector {
+    Index(Hash256),
+    Account { account: Address, ticket_seq: u32 },
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct LedgerEntryResponse {
+    /// The unique ID of this ledger object.
+    pub index: Hash256,
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+    /// (Omitted if binary is true) The contents of the ledger object.
+    pub node: Option<LedgerEntry>,
+    /// (Omitted unless binary is true) The binary representation of the ledger object, as hexadecimal.
+    pub node_binary: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        EscrowSelector, LedgerDataRequest, LedgerDataResponse, LedgerDataState, LedgerEntryRequest,
+        LedgerEntryResponse, LedgerEntrySelector, LedgerRequest, LedgerRequestIndex,
+    };
+    use crate::types::{CurrencyAmount, LedgerEntry};
+    use serde_json::json;
+
+    #[test]
+    fn ledger_request_index() {
+        // The validated ledger must be requested by name, as omitting the index selects the
+        // current open ledger.
+        let request = LedgerRequest::default();
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({ "ledger_index": "validated" })
+        );
+        let request = LedgerRequest {
+            ledger_index: LedgerRequestIndex::Index(6885842),
+            ..Default::default()
+        };
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value, json!({ "ledger_index": 6885842 }));
+        assert_eq!(serde_json::from_value::<LedgerRequest>(value).unwrap(), request);
+        assert_eq!(
+            serde_json::from_value::<LedgerRequestIndex>(json!("validated")).unwrap(),
+            LedgerRequestIndex::Validated
+        );
+        assert!(serde_json::from_value::<LedgerRequestIndex>(json!("current")).is_err());
+    }
+
+    #[test]
+    fn ledger_data_pages() {
+        let index = "0000041EFD027808D3F78C8352F97E324CB816318E00B977C74ECDDC7CD975B2";
+        let res: LedgerDataResponse = serde_json::from_value(json!({
+            "ledger_hash": "842B57C1CC0613299A686D3E9F310EC0422C84D3911E5056389AA7E5808A93C8",
+            "ledger_index": "6885842",
+            "marker": "0002A4C4025B3A5D8B48744B7F7F7E5F2EA2A6C29E6A5F16C5AEEFA56C24D9A8",
+            "state": [{
+                "Account": "rKKzk9ghA2iuy3imqMXUHJqdRPMtNDGf4c",
+                "Balance": "893730848",
+                "Flags": 0,
+                "LedgerEntryType": "AccountRoot",
+                "OwnerCount": 0,
+                "PreviousTxnID": "C204A65CF2542946289A3358C67D991B5E135FABFA89F271DBA7A150C08CA046",
+                "PreviousTxnLgrSeq": 6487716,
+                "Sequence": 1,
+                "index": index
+            }]
+        }))
+        .unwrap();
+        match &res.state {
+            LedgerDataState::Json(state) => match &state[0] {
+                LedgerEntry::AccountRoot(account) => {
+                    assert_eq!(account.balance, CurrencyAmount::xrp(893730848))
+                }
+                entry => panic!("expected an AccountRoot, got {:?}", entry),
+            },
+            state => panic!("expected parsed objects, got {:?}", state),
+        }
+        let request = LedgerDataRequest {
+            binary: Some(true),
+            ..Default::default()
+        };
+        let next = request.next_page(&res).unwrap();
+        assert_eq!(next.pagination.marker, res.pagination.marker);
+        assert_eq!(
+            serde_json::to_value(&next).unwrap()["marker"],
+            json!("0002A4C4025B3A5D8B48744B7F7F7E5F2EA2A6C29E6A5F16C5AEEFA56C24D9A8")
+        );
+        let res: LedgerDataResponse = serde_json::from_value(json!({
+            "ledger_index": 6885842,
+            "state": [{ "data": "1100612200000000", "index": index }]
+        }))
+        .unwrap();
+        match &res.state {
+            LedgerDataState::Binary(state) => assert_eq!(state[0].index, index),
+            state => panic!("expected binary objects, got {:?}", state),
+        }
+        assert!(request.next_page(&res).is_none());
+    }
+
+    #[test]
+    fn ledger_entry() {
+        let channel = "5DB01B7FFED6B67E6B0414DED11E051D2EE2B7619CE0EAA6286D67A3A4D5BDB3";
+        let request = LedgerEntryRequest::payment_channel(channel).unwrap();
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({ "payment_channel": channel })
+        );
+        assert!(LedgerEntryRequest::payment_channel(&channel[1..]).is_err());
+        let request = LedgerEntryRequest {
+            binary: Some(true),
+            ..LedgerEntryRequest::new(LedgerEntrySelector::Escrow(EscrowSelector::Owner {
+                owner: "rL4fPHi2FWGwRGRQSH7gBcxkuo2b9NTjKK".to_owned(),
+                seq: 126,
+            }))
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "escrow": { "owner": "rL4fPHi2FWGwRGRQSH7gBcxkuo2b9NTjKK", "seq": 126 },
+                "binary": true
+            })
+        );
+        let res: LedgerEntryResponse = serde_json::from_value(json!({
+            "index": channel,
+            "ledger_index": 61966146,
+            "node": {
+                "Account": "rN7n7otQDd6FczFgLdSqtcsAUxDkw6fzRH",
+                "Amount": "1000",
+                "Balance": "0",
+                "Destination": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn",
+                "Flags": 0,
+                "LedgerEntryType": "PayChannel",
+                "PublicKey": "32D2471DB72B27E3310F355BB33E339BF26F8392D5A93D3BC0FC3B566612DA0F0A",
+                "SettleDelay": 86400,
+                "index": channel
+            },
+            "validated": true
+        }))
+        .unwrap();
+        assert_eq!(res.index.to_string(), channel);
+        match res.node {
+            Some(LedgerEntry::PayChannel(channel)) => assert_eq!(channel.settle_delay, 86400),
+            node => panic!("expected a PayChannel, got {:?}", node),
+        }
+    }
+}
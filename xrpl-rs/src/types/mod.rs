@@ -1,30 +1,92 @@
 pub mod account;
+pub mod amendments;
+pub mod book;
 pub mod fee;
+pub mod gateway;
 pub mod ledger;
+pub mod path;
 pub mod submit;
 pub mod channels;
 pub mod tx;
 pub mod subscribe;
+pub mod server;
 
 use std::convert::{TryFrom, TryInto};
 use std::num::ParseIntError;
-use std::ops::Add;
+use std::ops::{Add, Sub};
 use std::str::FromStr;
 
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use serde;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_with::skip_serializing_none;
 
+pub use serde_xrpl::utils::RoundingMode;
+
+/// The number of drops in one XRP.
+pub const DROPS_PER_XRP: u64 = 1_000_000;
+
+/// The total supply of XRP, in drops. No XRP amount can be larger.
+pub const MAX_XRP_DROPS: u64 = 100_000_000_000_000_000;
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Default, Clone)]
 pub struct BigInt(pub u64);
 
+impl BigInt {
+    /// Adds `other`, returning `None` on overflow.
+    pub fn checked_add(&self, other: &BigInt) -> Option<BigInt> {
+        self.0.checked_add(other.0).map(BigInt)
+    }
+    /// Subtracts `other`, returning `None` if it is larger.
+    pub fn checked_sub(&self, other: &BigInt) -> Option<BigInt> {
+        self.0.checked_sub(other.0).map(BigInt)
+    }
+    /// Returns whether this is a valid amount of XRP in drops, i.e. no more than `MAX_XRP_DROPS`.
+    pub fn is_valid_drops(&self) -> bool {
+        self.0 <= MAX_XRP_DROPS
+    }
+    /// Converts an amount of XRP, e.g. `"1.5"`, to drops. See `try_from_xrp`.
+    pub fn try_from_xrp_str(xrp: &str) -> Result<Self, crate::Error> {
+        let xrp = Decimal::from_str(xrp)
+            .map_err(|_| crate::ValidationError::InvalidAmount(xrp.to_owned()))?;
+        Self::try_from_xrp(xrp)
+    }
+    /// Converts an amount of XRP to drops. Negative amounts, amounts with fractional drops, i.e.
+    /// more than 6 decimal places, and amounts above the total supply of XRP are rejected.
+    pub fn try_from_xrp(xrp: Decimal) -> Result<Self, crate::Error> {
+        let invalid =
+            || -> crate::Error { crate::ValidationError::InvalidAmount(xrp.to_string()).into() };
+        let drops = xrp
+            .checked_mul(Decimal::from(DROPS_PER_XRP))
+            .filter(|drops| drops.fract().is_zero())
+            .ok_or_else(invalid)?;
+        drops
+            .to_u64()
+            .map(BigInt)
+            .filter(BigInt::is_valid_drops)
+            .ok_or_else(invalid)
+    }
+}
+
 impl From<u64> for BigInt {
     fn from(v: u64) -> Self {
         Self(v)
     }
-} 
+}
+
+impl From<u32> for BigInt {
+    fn from(v: u32) -> Self {
+        Self(v.into())
+    }
+}
+
+impl From<u16> for BigInt {
+    fn from(v: u16) -> Self {
+        Self(v.into())
+    }
+}
 
 impl std::ops::Deref for BigInt {
     type Target = u64;
@@ -48,7 +110,7 @@ impl<'de> Deserialize<'de> for BigInt {
     where
         D: serde::de::Deserializer<'de>,
     {
-        deserializer.deserialize_str(BigIntVisitor)
+        deserializer.deserialize_any(BigIntVisitor)
     }
 }
 
@@ -58,7 +120,23 @@ impl<'de> serde::de::Visitor<'de> for BigIntVisitor {
     type Value = BigInt;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("an unsigned integer")
+        formatter.write_str("an unsigned integer or a string containing one")
+    }
+
+    fn visit_u64<E>(self, value: u64) -> std::result::Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BigInt(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> std::result::Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BigInt(value.try_into().map_err(|e| {
+            serde::de::Error::custom(format!("{:?}", e))
+        })?))
     }
 
     fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
@@ -74,11 +152,206 @@ impl<'de> serde::de::Visitor<'de> for BigIntVisitor {
 /// An address used to identify an account.
 pub type Address = String;
 
+/// A classic address whose base58 checksum has been verified.
+///
+/// Request types use this so that a malformed address is rejected when the request is built
+/// rather than by the server, and take it in their constructors so that no request is built
+/// without one. Response types keep using `Address` so that parsing stays lenient.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct ClassicAddress(String);
+
+impl ClassicAddress {
+    /// Validates the address, checking its length, account id prefix and checksum.
+    pub fn new(address: &str) -> Result<Self, crate::Error> {
+        match bs58::decode(address)
+            .with_alphabet(bs58::Alphabet::RIPPLE)
+            .with_check(None)
+            .into_vec()
+        {
+            Ok(decoded) if decoded.len() == 21 && decoded[0] == 0x00 => {
+                Ok(Self(address.to_owned()))
+            }
+            _ => Err(crate::ValidationError::InvalidAddress(address.to_owned()).into()),
+        }
+    }
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for ClassicAddress {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl From<ClassicAddress> for Address {
+    fn from(address: ClassicAddress) -> Self {
+        address.0
+    }
+}
+
+impl std::fmt::Display for ClassicAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ClassicAddress {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let address = String::deserialize(deserializer)?;
+        Self::new(&address)
+            .map_err(|_| serde::de::Error::custom(format!("invalid address: {}", address)))
+    }
+}
+
+/// The prefix of X-addresses for the main network.
+const X_ADDRESS_MAIN_PREFIX: [u8; 2] = [0x05, 0x44];
+/// The prefix of X-addresses for test networks.
+const X_ADDRESS_TEST_PREFIX: [u8; 2] = [0x04, 0x93];
+
+/// An X-address, which packs a classic address, an optional destination tag and whether it is for
+/// a test network into one string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XAddress {
+    /// The classic address of the account.
+    pub classic_address: ClassicAddress,
+    /// The destination tag, if any.
+    pub tag: Option<u32>,
+    /// Whether the address is for a test network rather than the main network.
+    pub test_network: bool,
+}
+
+impl XAddress {
+    /// Decodes an X-address, checking its checksum, prefix and reserved bytes.
+    pub fn new(x_address: &str) -> Result<Self, crate::Error> {
+        let invalid = || -> crate::Error {
+            crate::ValidationError::InvalidAddress(x_address.to_owned()).into()
+        };
+        let decoded = bs58::decode(x_address)
+            .with_alphabet(bs58::Alphabet::RIPPLE)
+            .with_check(None)
+            .into_vec()
+            .map_err(|_| invalid())?;
+        if decoded.len() != 31 {
+            return Err(invalid());
+        }
+        let test_network = match [decoded[0], decoded[1]] {
+            X_ADDRESS_MAIN_PREFIX => false,
+            X_ADDRESS_TEST_PREFIX => true,
+            _ => return Err(invalid()),
+        };
+        let tag = u32::from_le_bytes([decoded[23], decoded[24], decoded[25], decoded[26]]);
+        let tag = match decoded[22] {
+            0 if tag == 0 => None,
+            1 => Some(tag),
+            _ => return Err(invalid()),
+        };
+        // The last 4 bytes are reserved for 64 bit tags, which are not supported.
+        if decoded[27..] != [0; 4] {
+            return Err(invalid());
+        }
+        let classic_address = bs58::encode([&[0x00], &decoded[2..22]].concat())
+            .with_alphabet(bs58::Alphabet::RIPPLE)
+            .with_check()
+            .into_string();
+        Ok(Self {
+            classic_address: ClassicAddress(classic_address),
+            tag,
+            test_network,
+        })
+    }
+}
+
+impl FromStr for XAddress {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
 /// A Marker can be used to paginate the server response. It's content is intentionally undefined. Each server can define a marker as desired.
-pub type Marker = Value;
+///
+/// Markers should be stored and passed back unchanged in the next request to resume pagination.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Marker(Value);
+
+impl Marker {
+    /// Returns the raw marker value, for logging or inspection.
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Marker {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
 pub type H256 = String;
 
+/// A 256-bit hash, such as a transaction hash, ledger object id or payment channel id. It is
+/// represented in JSON as 64 hex characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Hash256(pub [u8; 32]);
+
+impl FromStr for Hash256 {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        validate_hash(s)?;
+        let mut hash = [0u8; 32];
+        hex::decode_to_slice(s, &mut hash)
+            .map_err(|_| crate::ValidationError::InvalidHash(s.to_owned()))?;
+        Ok(Self(hash))
+    }
+}
+
+impl std::fmt::Display for Hash256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", hex::encode_upper(self.0))
+    }
+}
+
+impl Serialize for Hash256 {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Hash256 {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let hash = String::deserialize(deserializer)?;
+        hash.parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid hash: {}", hash)))
+    }
+}
+
+/// Checks that a ledger or transaction hash is 64 hex characters, so that a mistyped hash fails
+/// locally instead of with an error from the server.
+pub fn validate_hash(hash: &str) -> Result<(), crate::Error> {
+    if hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(())
+    } else {
+        Err(crate::ValidationError::InvalidHash(hash.to_owned()).into())
+    }
+}
+
 /// Unique request id.
 ///
 /// NOTE Assigning same id to different requests will cause the previous request to be unsubscribed.
@@ -97,6 +370,17 @@ pub struct LedgerInfo {
     pub validated: Option<bool>,
 }
 
+impl LedgerInfo {
+    /// Selects the ledger with the provided hash.
+    pub fn with_ledger_hash(ledger_hash: &str) -> Result<Self, crate::Error> {
+        validate_hash(ledger_hash)?;
+        Ok(Self {
+            ledger_hash: Some(ledger_hash.to_owned()),
+            ..Default::default()
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Integer(pub u32);
 
@@ -134,6 +418,13 @@ pub struct PaginationInfo {
     pub marker: Option<Marker>,
 }
 
+impl PaginationInfo {
+    /// Returns true if there are no further pages to fetch.
+    pub fn is_done(&self) -> bool {
+        self.marker.is_none()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct JsonRPCResponse<T> {
     pub result: JsonRPCResponseResult<T>,
@@ -184,11 +475,96 @@ pub struct WebsocketSuccessResponse<T> {
     pub forwarded: Option<bool>,
 }
 
+macro_rules! api_error_codes {
+    ($($variant: ident => $code: expr),* $(,)?) => {
+        /// An error code returned by the API in the `error` field of a response, e.g. actNotFound.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(from = "String", into = "String")]
+        pub enum ApiErrorCode {
+            $($variant,)*
+            /// An error code that does not have a dedicated variant.
+            Other(String),
+        }
+
+        impl ApiErrorCode {
+            /// Returns the error code as it is represented by rippled, e.g. actNotFound.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $code,)*
+                    Self::Other(code) => code,
+                }
+            }
+        }
+
+        impl From<&str> for ApiErrorCode {
+            fn from(code: &str) -> Self {
+                match code {
+                    $($code => Self::$variant,)*
+                    _ => Self::Other(code.to_owned()),
+                }
+            }
+        }
+    };
+}
+
+api_error_codes!(
+    ActMalformed => "actMalformed",
+    ActNotFound => "actNotFound",
+    AmendmentBlocked => "amendmentBlocked",
+    BadSecret => "badSecret",
+    BadSeed => "badSeed",
+    EntryNotFound => "entryNotFound",
+    Forbidden => "forbidden",
+    HighFee => "highFee",
+    Internal => "internal",
+    InvalidParams => "invalidParams",
+    LgrIdxsInvalid => "lgrIdxsInvalid",
+    LgrIdxMalformed => "lgrIdxMalformed",
+    LgrNotFound => "lgrNotFound",
+    NoClosed => "noClosed",
+    NoCurrent => "noCurrent",
+    NoNetwork => "noNetwork",
+    NoPermission => "noPermission",
+    NotReady => "notReady",
+    NotSynced => "notSynced",
+    SlowDown => "slowDown",
+    SrcActNotFound => "srcActNotFound",
+    TooBusy => "tooBusy",
+    TxnNotFound => "txnNotFound",
+    UnknownCmd => "unknownCmd",
+);
+
+impl From<String> for ApiErrorCode {
+    fn from(code: String) -> Self {
+        Self::from(code.as_str())
+    }
+}
+
+impl From<ApiErrorCode> for String {
+    fn from(code: ApiErrorCode) -> Self {
+        code.as_str().to_owned()
+    }
+}
+
+impl std::fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ErrorResponse {
     pub id: Option<RequestId>,
     pub r#type: Option<String>,
-    pub error: Option<String>,
+    pub error: Option<ApiErrorCode>,
+    /// (May be omitted) The numeric code of the error.
+    pub error_code: Option<i64>,
+    /// (May be omitted) A human-readable description of the error.
+    pub error_message: Option<String>,
+    /// (May be omitted) For a `txnNotFound` error from a `tx` request with a ledger range, whether
+    /// the server searched every ledger in the range. If true, the transaction was definitely not
+    /// validated in that range.
+    pub searched_all: Option<bool>,
 }
 
 
@@ -227,12 +603,25 @@ pub struct SignerEntry {
 pub enum CurrencyAmount {
     XRP(BigInt),
     IssuedCurrency(IssuedCurrencyAmount),
+    MPT(MPTAmount),
 }
 
 impl CurrencyAmount {
     pub fn xrp(drops: u64) -> Self {
         Self::XRP(BigInt(drops))
     }
+    /// Creates an XRP amount from a number of XRP rather than drops, e.g. `Decimal::new(15, 1)`
+    /// for 1.5 XRP. The same amounts as `BigInt::try_from_xrp` are rejected.
+    pub fn xrp_from_decimal(xrp: Decimal) -> Result<Self, crate::Error> {
+        Ok(Self::XRP(BigInt::try_from_xrp(xrp)?))
+    }
+    /// Returns an XRP amount as a number of XRP rather than drops, or `None` for other amounts.
+    pub fn as_xrp_decimal(&self) -> Option<Decimal> {
+        match self {
+            Self::XRP(drops) => Some(Decimal::from_i128_with_scale(drops.0 as i128, 6).normalize()),
+            _ => None,
+        }
+    }
     pub fn issued_currency(value: Decimal, currency: &str, issuer: &Address) -> Self {
         Self::IssuedCurrency(IssuedCurrencyAmount {
             value,
@@ -240,6 +629,85 @@ impl CurrencyAmount {
             issuer: issuer.to_owned(),
         })
     }
+    /// Creates an issued currency amount for a non-standard currency code, given as a 40 character
+    /// hex code such as `0158415500000000C1F76FF6ECB0BAC600000000`. Use `currency_name_to_hex` to
+    /// encode a currency name longer than 3 characters.
+    pub fn issued_currency_hex(
+        value: Decimal,
+        currency_hex: &str,
+        issuer: &Address,
+    ) -> Result<Self, crate::Error> {
+        if currency_hex.len() != 40
+            || serde_xrpl::utils::encode_currency_code(currency_hex).is_err()
+        {
+            return Err(
+                crate::ValidationError::InvalidCurrencyCode(currency_hex.to_owned()).into(),
+            );
+        }
+        Ok(Self::issued_currency(
+            value,
+            &currency_hex.to_uppercase(),
+            issuer,
+        ))
+    }
+    /// Creates an issued currency amount, rounding the value to the precision the XRPL can
+    /// represent with the provided rounding mode. Use `RoundingMode::Down` for amounts being sent,
+    /// so that rounding never spends more than requested.
+    pub fn issued_currency_with_rounding(
+        value: Decimal,
+        currency: &str,
+        issuer: &Address,
+        rounding_mode: RoundingMode,
+    ) -> Self {
+        Self::issued_currency(
+            serde_xrpl::utils::round_issued_currency_amount(value, rounding_mode),
+            currency,
+            issuer,
+        )
+    }
+    /// Returns whether the amount is zero drops of XRP or zero of an issued currency.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Self::XRP(drops) => drops.0 == 0,
+            Self::IssuedCurrency(amount) => amount.value.is_zero(),
+            Self::MPT(amount) => amount.value.trim_start_matches('0').is_empty(),
+        }
+    }
+    /// Formats the amount for human readable output such as logs and receipts.
+    ///
+    /// XRP amounts are converted from drops to XRP, e.g. `10.5 XRP`. Issued currency amounts
+    /// include an abbreviated issuer, e.g. `7072.8 USD (rvYAf...)`.
+    pub fn to_display_string(&self) -> String {
+        match self {
+            Self::XRP(drops) => format!(
+                "{} XRP",
+                Decimal::from_i128_with_scale(drops.0 as i128, 6).normalize()
+            ),
+            Self::IssuedCurrency(amount) => format!(
+                "{} {} ({}...)",
+                amount.value.normalize(),
+                amount.currency,
+                amount.issuer.chars().take(5).collect::<String>()
+            ),
+            Self::MPT(amount) => format!(
+                "{} MPT ({}...)",
+                amount.value,
+                amount.mpt_issuance_id.chars().take(5).collect::<String>()
+            ),
+        }
+    }
+}
+
+/// Encodes a currency name longer than 3 characters, e.g. `"MyToken"`, as the 40 character hex
+/// code used for non-standard currencies. Names of 3 characters or fewer, which are standard or
+/// invalid codes, and names longer than 20 bytes are rejected.
+pub fn currency_name_to_hex(name: &str) -> Result<String, crate::Error> {
+    let mut bytes = name.as_bytes().to_vec();
+    if bytes.len() <= 3 || bytes.len() > 20 {
+        return Err(crate::ValidationError::InvalidCurrencyCode(name.to_owned()).into());
+    }
+    bytes.resize(20, 0u8);
+    Ok(hex::encode_upper(bytes))
 }
 
 impl Default for CurrencyAmount {
@@ -248,6 +716,91 @@ impl Default for CurrencyAmount {
     }
 }
 
+/// The reasons adding or subtracting two `CurrencyAmount`s can fail.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CurrencyAmountError {
+    /// The amounts are of different assets, i.e. XRP and an issued currency, or issued currencies
+    /// or tokens with a different currency, issuer or issuance.
+    MismatchedAssets,
+    /// The result is out of range, e.g. more drops than fit in a `u64` or fewer than zero.
+    Overflow,
+    /// A token amount could not be parsed as an integer.
+    InvalidAmount(String),
+}
+
+impl CurrencyAmount {
+    /// Applies `xrp` to the drops of XRP amounts and token amounts, and `issued` to the values of
+    /// issued currency amounts, if both amounts are of the same asset.
+    fn combine(
+        &self,
+        other: &CurrencyAmount,
+        xrp: fn(&BigInt, &BigInt) -> Option<BigInt>,
+        issued: fn(Decimal, Decimal) -> Option<Decimal>,
+    ) -> Result<CurrencyAmount, CurrencyAmountError> {
+        match (self, other) {
+            (Self::XRP(a), Self::XRP(b)) => xrp(a, b)
+                .map(Self::XRP)
+                .ok_or(CurrencyAmountError::Overflow),
+            (Self::IssuedCurrency(a), Self::IssuedCurrency(b))
+                if a.currency == b.currency && a.issuer == b.issuer =>
+            {
+                let value = issued(a.value, b.value).ok_or(CurrencyAmountError::Overflow)?;
+                Ok(Self::IssuedCurrency(IssuedCurrencyAmount {
+                    value,
+                    ..a.clone()
+                }))
+            }
+            (Self::MPT(a), Self::MPT(b)) if a.mpt_issuance_id == b.mpt_issuance_id => {
+                let parse = |value: &str| {
+                    value
+                        .parse()
+                        .map(BigInt)
+                        .map_err(|_| CurrencyAmountError::InvalidAmount(value.to_owned()))
+                };
+                let value = xrp(&parse(&a.value)?, &parse(&b.value)?)
+                    .ok_or(CurrencyAmountError::Overflow)?;
+                Ok(Self::MPT(MPTAmount {
+                    value: value.0.to_string(),
+                    ..a.clone()
+                }))
+            }
+            _ => Err(CurrencyAmountError::MismatchedAssets),
+        }
+    }
+}
+
+impl Add for &CurrencyAmount {
+    type Output = Result<CurrencyAmount, CurrencyAmountError>;
+
+    fn add(self, other: &CurrencyAmount) -> Self::Output {
+        self.combine(other, BigInt::checked_add, Decimal::checked_add)
+    }
+}
+
+impl Add for CurrencyAmount {
+    type Output = Result<CurrencyAmount, CurrencyAmountError>;
+
+    fn add(self, other: CurrencyAmount) -> Self::Output {
+        &self + &other
+    }
+}
+
+impl Sub for &CurrencyAmount {
+    type Output = Result<CurrencyAmount, CurrencyAmountError>;
+
+    fn sub(self, other: &CurrencyAmount) -> Self::Output {
+        self.combine(other, BigInt::checked_sub, Decimal::checked_sub)
+    }
+}
+
+impl Sub for CurrencyAmount {
+    type Output = Result<CurrencyAmount, CurrencyAmountError>;
+
+    fn sub(self, other: CurrencyAmount) -> Self::Output {
+        &self - &other
+    }
+}
+
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct IssuedCurrencyAmount {
     pub value: Decimal,
@@ -255,6 +808,37 @@ pub struct IssuedCurrencyAmount {
     pub issuer: Address,
 }
 
+impl IssuedCurrencyAmount {
+    /// Parses and validates an issued currency amount from user input. The value must be a
+    /// decimal number with no more significant digits than the XRPL can represent, the currency a
+    /// 3 character code other than XRP or a 40 character hex code, and the issuer a valid classic
+    /// address.
+    pub fn new(value: &str, currency: &str, issuer: &str) -> Result<Self, crate::Error> {
+        let invalid_amount = || crate::ValidationError::InvalidAmount(value.to_owned());
+        let value = Decimal::from_str(value).map_err(|_| invalid_amount())?;
+        if serde_xrpl::utils::round_issued_currency_amount(value, RoundingMode::Down) != value {
+            return Err(invalid_amount().into());
+        }
+        if currency == "XRP" || serde_xrpl::utils::encode_currency_code(currency).is_err() {
+            return Err(crate::ValidationError::InvalidCurrencyCode(currency.to_owned()).into());
+        }
+        Ok(Self {
+            value,
+            currency: currency.to_owned(),
+            issuer: ClassicAddress::new(issuer)?.into(),
+        })
+    }
+}
+
+/// An amount of a Multi-Purpose Token (Added by the MPTokensV1 amendment).
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct MPTAmount {
+    /// The amount of tokens, as a quoted integer in the token's smallest unit.
+    pub value: String,
+    /// The ID of the MPTokenIssuance the tokens belong to, as hex.
+    pub mpt_issuance_id: String,
+}
+
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct TransactionEntryRequest {
     pub tx_hash: Option<String>,
@@ -262,6 +846,20 @@ pub struct TransactionEntryRequest {
     pub ledger_hash: Option<String>,
 }
 
+impl TransactionEntryRequest {
+    /// Creates a request for the transaction with the provided hash in the ledger with the
+    /// provided hash.
+    pub fn new(tx_hash: &str, ledger_hash: &str) -> Result<Self, crate::Error> {
+        validate_hash(tx_hash)?;
+        validate_hash(ledger_hash)?;
+        Ok(Self {
+            tx_hash: Some(tx_hash.to_owned()),
+            ledger_hash: Some(ledger_hash.to_owned()),
+            ..Default::default()
+        })
+    }
+}
+
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct TransactionEntryResponse {
     pub tx_json: Option<Value>,
@@ -272,9 +870,18 @@ pub struct TransactionEntryResponse {
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 #[serde(tag = "LedgerEntryType")]
 pub enum LedgerEntry {
-    Unknown,
     AccountRoot(AccountRoot),
+    Amendments(amendments::Amendments),
     Check(Check),
+    DepositPreauth(DepositPreauth),
+    Escrow(Escrow),
+    Offer(Offer),
+    PayChannel(PayChannel),
+    RippleState(RippleState),
+    SignerList(SignerList),
+    Ticket(Ticket),
+    #[serde(other)]
+    Unknown,
 }
 
 impl Default for LedgerEntry {
@@ -296,13 +903,13 @@ pub struct AccountRoot {
     pub owner_count: u32,
     /// The identifying hash of the transaction that most recently modified this object.
     #[serde(rename = "PreviousTxnID")]
-    pub previous_txn_id: H256,
+    pub previous_txn_id: Hash256,
     /// The index of the ledger that contains the transaction that most recently modified this object.
     pub previous_txn_lgr_seq: u32,
     /// The sequence number of the next valid transaction for this account.
     pub sequence: u32,
     /// (Optional) The identifying hash of the transaction most recently sent by this account. This field must be enabled to use the AccountTxnID transaction field. To enable it, send an AccountSet transaction with the asfAccountTxnID flag enabled.
-    pub account_txn_id: Option<H256>,
+    pub account_txn_id: Option<Hash256>,
     /// (Optional) A domain associated with this account. In JSON, this is the hexadecimal for the ASCII representation of the domain. Cannot be more than 256 bytes in length.
     pub domain: Option<String>,
     /// (Optional) The md5 hash of an email address. Clients can use this to look up an avatar through services such as Gravatar .
@@ -317,6 +924,29 @@ pub struct AccountRoot {
     pub tick_size: Option<u8>,
     /// (Optional) A transfer fee to charge other users for sending currency issued by this account to each other.
     pub transfer_rate: Option<u32>,
+    /// (Optional) Another account that is authorized to mint NFTokens on behalf of this account.
+    #[serde(rename = "NFTokenMinter")]
+    pub nftoken_minter: Option<Address>,
+    /// (Optional) How many total NFTokens have been minted by and on behalf of this account.
+    #[serde(rename = "MintedNFTokens")]
+    pub minted_nftokens: Option<u32>,
+    /// (Optional) How many total of this account's issued NFTokens have been burned. This number is always equal or less than MintedNFTokens.
+    #[serde(rename = "BurnedNFTokens")]
+    pub burned_nftokens: Option<u32>,
+    /// (Optional) The account's Sequence Number at the time it minted its first non-fungible-token.
+    #[serde(rename = "FirstNFTokenSequence")]
+    pub first_nftoken_sequence: Option<u32>,
+}
+
+impl AccountRoot {
+    /// Returns the XRP, in drops, this account must hold in reserve given the base and owner
+    /// reserve, in drops, of the current ledger.
+    ///
+    /// Every owned object counts once towards `owner_count`, including Tickets and NFToken pages,
+    /// so `ticket_count` and the NFToken counters must not be added to it.
+    pub fn reserve(&self, reserve_base: u64, reserve_inc: u64) -> BigInt {
+        BigInt(reserve_base + self.owner_count as u64 * reserve_inc)
+    }
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
@@ -329,3 +959,562 @@ pub struct Check {
     /// A bit-map of boolean flags enabled for this account.
     pub flags: u32,
 }
+
+/// A trust line between two accounts. The account with the numerically lower address is the
+/// "low" account and the other is the "high" account.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct RippleState {
+    /// The balance of the trust line, from the perspective of the low account. A negative balance indicates that the high account holds tokens issued by the low account. The issuer in this is always set to the neutral value ACCOUNT_ONE.
+    pub balance: IssuedCurrencyAmount,
+    /// A bit-map of boolean options enabled for this object.
+    pub flags: u32,
+    /// The limit that the high account has set on the trust line. The issuer is the address of the high account that set this limit.
+    pub high_limit: IssuedCurrencyAmount,
+    /// (Omitted in some historical ledgers) A hint indicating which page of the high account's owner directory links to this object, in case the directory consists of multiple pages.
+    pub high_node: Option<String>,
+    /// (Optional) The inbound quality set by the high account, as an integer in the implied ratio HighQualityIn:1,000,000,000. As a special case, the value 0 is equivalent to 1 billion, or face value.
+    pub high_quality_in: Option<u32>,
+    /// (Optional) The outbound quality set by the high account, as an integer in the implied ratio HighQualityOut:1,000,000,000. As a special case, the value 0 is equivalent to 1 billion, or face value.
+    pub high_quality_out: Option<u32>,
+    /// The limit that the low account has set on the trust line. The issuer is the address of the low account that set this limit.
+    pub low_limit: IssuedCurrencyAmount,
+    /// (Omitted in some historical ledgers) A hint indicating which page of the low account's owner directory links to this object, in case the directory consists of multiple pages.
+    pub low_node: Option<String>,
+    /// (Optional) The inbound quality set by the low account, as an integer in the implied ratio LowQualityIn:1,000,000,000. As a special case, the value 0 is equivalent to 1 billion, or face value.
+    pub low_quality_in: Option<u32>,
+    /// (Optional) The outbound quality set by the low account, as an integer in the implied ratio LowQualityOut:1,000,000,000. As a special case, the value 0 is equivalent to 1 billion, or face value.
+    pub low_quality_out: Option<u32>,
+    /// The identifying hash of the transaction that most recently modified this object.
+    #[serde(rename = "PreviousTxnID")]
+    pub previous_txn_id: Option<Hash256>,
+    /// The index of the ledger that contains the transaction that most recently modified this object.
+    pub previous_txn_lgr_seq: Option<u32>,
+}
+
+impl RippleState {
+    /// Returns the balance of the trust line from the perspective of the provided account. A
+    /// positive balance means the account holds tokens issued by the other account, a negative
+    /// balance means the other account holds tokens issued by it. Returns zero if the account is
+    /// not one of the two accounts of the trust line.
+    pub fn balance_for(&self, account: &Address) -> Decimal {
+        if account == &self.low_limit.issuer {
+            self.balance.value
+        } else if account == &self.high_limit.issuer {
+            -self.balance.value
+        } else {
+            Decimal::ZERO
+        }
+    }
+}
+
+/// A preauthorization for one account to send payments to another account that requires deposit
+/// authorization.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct DepositPreauth {
+    /// The account that granted the preauthorization. (The destination of the preauthorized payments.)
+    pub account: Address,
+    /// The account that received the preauthorization. (The sender of the preauthorized payments.)
+    pub authorize: Address,
+    /// A bit-map of boolean flags. No flags are defined for DepositPreauth objects, so this value is always 0.
+    pub flags: u32,
+    /// A hint indicating which page of the sender's owner directory links to this object, in case the directory consists of multiple pages.
+    pub owner_node: Option<String>,
+    /// The identifying hash of the transaction that most recently modified this object.
+    #[serde(rename = "PreviousTxnID")]
+    pub previous_txn_id: Option<Hash256>,
+    /// The index of the ledger that contains the transaction that most recently modified this object.
+    pub previous_txn_lgr_seq: Option<u32>,
+}
+
+/// XRP held in escrow, to be delivered to the destination once its conditions are met.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Escrow {
+    /// The address of the owner (sender) of this escrow. This is the account that provided the XRP, and gets it back if the escrow is canceled.
+    pub account: Address,
+    /// The amount of XRP, in drops, currently held in the escrow.
+    pub amount: CurrencyAmount,
+    /// (Optional) The escrow can be canceled if and only if this field is present and the time it specifies has passed. Specifically, this is specified as seconds since the Ripple Epoch and it "has passed" if it's earlier than the close time of the previous validated ledger.
+    pub cancel_after: Option<u32>,
+    /// (Optional) A PREIMAGE-SHA-256 crypto-condition, as hexadecimal. If present, the EscrowFinish transaction must contain a fulfillment that satisfies this condition.
+    pub condition: Option<String>,
+    /// The destination address where the XRP is paid if the escrow is successful.
+    pub destination: Address,
+    /// (Optional) A hint indicating which page of the destination's owner directory links to this object, in case the directory consists of multiple pages. Omitted on escrows created before enabling the fix1523 amendment.
+    pub destination_node: Option<String>,
+    /// (Optional) An arbitrary tag to further specify the destination for this escrow, such as a hosted recipient at the destination address.
+    pub destination_tag: Option<u32>,
+    /// (Optional) The time, in seconds since the Ripple Epoch, after which this escrow can be finished. Any EscrowFinish transaction before this time fails.
+    pub finish_after: Option<u32>,
+    /// A bit-map of boolean flags. No flags are defined for the Escrow type, so this value is always 0.
+    pub flags: u32,
+    /// A hint indicating which page of the sender's owner directory links to this object, in case the directory consists of multiple pages.
+    pub owner_node: Option<String>,
+    /// The identifying hash of the transaction that most recently modified this object.
+    #[serde(rename = "PreviousTxnID")]
+    pub previous_txn_id: Option<Hash256>,
+    /// The index of the ledger that contains the transaction that most recently modified this object.
+    pub previous_txn_lgr_seq: Option<u32>,
+    /// (Optional) An arbitrary tag to further specify the source for this escrow, such as a hosted recipient at the owner's address.
+    pub source_tag: Option<u32>,
+}
+
+/// An offer to exchange currencies in the decentralized exchange.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Offer {
+    /// The address of the account that owns this Offer.
+    pub account: Address,
+    /// The ID of the Offer Directory that links to this Offer.
+    pub book_directory: Hash256,
+    /// A hint indicating which page of the Offer Directory links to this object, in case the directory consists of multiple pages.
+    pub book_node: Option<String>,
+    /// (Optional) Indicates the time after which this Offer is considered unfunded. See Specifying Time for details.
+    pub expiration: Option<u32>,
+    /// A bit-map of boolean flags enabled for this Offer.
+    pub flags: u32,
+    /// A hint indicating which page of the Owner Directory links to this object, in case the directory consists of multiple pages.
+    pub owner_node: Option<String>,
+    /// The identifying hash of the transaction that most recently modified this object.
+    #[serde(rename = "PreviousTxnID")]
+    pub previous_txn_id: Option<Hash256>,
+    /// The index of the ledger that contains the transaction that most recently modified this object.
+    pub previous_txn_lgr_seq: Option<u32>,
+    /// The Sequence value of the OfferCreate transaction that created this Offer object. Used in combination with the Account to identify this Offer.
+    pub sequence: u32,
+    /// The remaining amount and type of currency being provided by the Offer creator.
+    pub taker_gets: CurrencyAmount,
+    /// The remaining amount and type of currency requested by the Offer creator.
+    pub taker_pays: CurrencyAmount,
+}
+
+/// A payment channel, which holds XRP that can be claimed by the destination using signed claims.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct PayChannel {
+    /// The source address that owns this payment channel. This comes from the sending address of the transaction that created the channel.
+    pub account: Address,
+    /// Total XRP, in drops, that has been allocated to this channel. This includes XRP that has been paid to the destination address.
+    pub amount: CurrencyAmount,
+    /// Total XRP, in drops, already paid out by the channel. The difference between this value and the Amount field is how much XRP can still be paid to the destination address with PaymentChannelClaim transactions. If the channel closes, the remaining difference is returned to the source address.
+    pub balance: CurrencyAmount,
+    /// (Optional) The immutable expiration time for this payment channel, in seconds since the Ripple Epoch. This channel is expired if this value is present and smaller than the previous ledger's close_time field.
+    pub cancel_after: Option<u32>,
+    /// The destination address for this payment channel. While the payment channel is open, this address is the only one that can receive XRP from the channel.
+    pub destination: Address,
+    /// (Optional) A hint indicating which page of the destination's owner directory links to this object, in case the directory consists of multiple pages. Omitted on payment channels created before enabling the fixPayChanRecipientOwnerDir amendment.
+    pub destination_node: Option<String>,
+    /// (Optional) An arbitrary tag to further specify the destination for this payment channel, such as a hosted recipient at the destination address.
+    pub destination_tag: Option<u32>,
+    /// (Optional) The mutable expiration time for this payment channel, in seconds since the Ripple Epoch. The channel is expired if this value is present and smaller than the previous ledger's close_time field.
+    pub expiration: Option<u32>,
+    /// A bit-map of boolean flags enabled for this payment channel. Currently, the protocol defines no flags for PayChannel objects.
+    pub flags: u32,
+    /// A hint indicating which page of the source address's owner directory links to this object, in case the directory consists of multiple pages.
+    pub owner_node: Option<String>,
+    /// The identifying hash of the transaction that most recently modified this object.
+    #[serde(rename = "PreviousTxnID")]
+    pub previous_txn_id: Option<Hash256>,
+    /// The index of the ledger that contains the transaction that most recently modified this object.
+    pub previous_txn_lgr_seq: Option<u32>,
+    /// Public key, in hexadecimal, of the key pair that can be used to sign claims against this channel.
+    pub public_key: String,
+    /// Number of seconds the source address must wait to close the channel if it still has any XRP in it.
+    pub settle_delay: u32,
+    /// (Optional) An arbitrary tag to further specify the source for this payment channel, such as a hosted recipient at the owner's address.
+    pub source_tag: Option<u32>,
+}
+
+/// A sequence number set aside for future use by a TicketCreate transaction.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Ticket {
+    /// The account that owns this Ticket.
+    pub account: Address,
+    /// A bit-map of Boolean flags enabled for this Ticket. Currently, there are no flags defined for Tickets.
+    pub flags: u32,
+    /// A hint indicating which page of the owner directory links to this object, in case the directory consists of multiple pages.
+    pub owner_node: Option<String>,
+    /// The identifying hash of the transaction that most recently modified this object.
+    #[serde(rename = "PreviousTxnID")]
+    pub previous_txn_id: Option<Hash256>,
+    /// The index of the ledger that contains the transaction that most recently modified this object.
+    pub previous_txn_lgr_seq: Option<u32>,
+    /// The Sequence Number this Ticket sets aside.
+    pub ticket_sequence: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        currency_name_to_hex, validate_hash, AccountRoot, ApiErrorCode, BigInt, ClassicAddress,
+        CurrencyAmount, CurrencyAmountError, ErrorResponse, Hash256, IssuedCurrencyAmount,
+        LedgerEntry, MPTAmount, XAddress, MAX_XRP_DROPS,
+    };
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    #[test]
+    fn big_int_arithmetic() {
+        assert_eq!(
+            BigInt::from(10u32).checked_add(&BigInt::from(2u16)),
+            Some(BigInt(12))
+        );
+        assert_eq!(BigInt(u64::MAX).checked_add(&BigInt(1)), None);
+        assert_eq!(BigInt(10).checked_sub(&BigInt(12)), None);
+        assert!(BigInt(MAX_XRP_DROPS).is_valid_drops());
+        assert!(!BigInt(MAX_XRP_DROPS + 1).is_valid_drops());
+    }
+
+    #[test]
+    fn xrp_decimal_conversions() {
+        let amount = CurrencyAmount::xrp_from_decimal(Decimal::new(15, 1)).unwrap();
+        assert_eq!(amount, CurrencyAmount::xrp(1_500_000));
+        assert_eq!(amount.as_xrp_decimal(), Some(Decimal::new(15, 1)));
+        assert_eq!(BigInt::try_from_xrp_str("0.000001").unwrap(), BigInt(1));
+        assert_eq!(
+            BigInt::try_from_xrp_str("100000000000").unwrap(),
+            BigInt(MAX_XRP_DROPS)
+        );
+        // Fractional drops, more than the total supply, negative amounts and non-numbers.
+        for xrp in ["0.0000001", "100000000000.000001", "-1", "one"] {
+            assert!(BigInt::try_from_xrp_str(xrp).is_err(), "{}", xrp);
+        }
+        let usd = CurrencyAmount::issued_currency(
+            Decimal::new(15, 1),
+            "USD",
+            &"rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned(),
+        );
+        assert_eq!(usd.as_xrp_decimal(), None);
+    }
+
+    #[test]
+    fn decode_x_address() {
+        let address = XAddress::new("XVLhHMPHU98es4dbozjVtdWzVrDjtV8xvjGQTYPiAx6gwDC").unwrap();
+        assert_eq!(
+            address.classic_address.as_str(),
+            "rGWrZyQqhTp9Xu7G5Pkayo7bXjH4k4QYpf"
+        );
+        assert_eq!(address.tag, Some(1));
+        assert!(!address.test_network);
+        let address = XAddress::new("XVLhHMPHU98es4dbozjVtdWzVrDjtV5fdx1mHp98tDMoQXb").unwrap();
+        assert_eq!(address.tag, None);
+        let address = XAddress::new("TVE26TYGhfLC7tQDno7G8dGtxSkYQn7h4mkJyVSGxTcrDFJ").unwrap();
+        assert_eq!(address.tag, Some(12345));
+        assert!(address.test_network);
+        assert!(XAddress::new("rGWrZyQqhTp9Xu7G5Pkayo7bXjH4k4QYpf").is_err());
+    }
+
+    #[test]
+    fn reserve_with_tickets_and_nftokens() {
+        // 1 trust line, 5 Tickets and 2 NFToken pages.
+        let account = AccountRoot {
+            owner_count: 8,
+            ticket_count: Some(5),
+            minted_nftokens: Some(40),
+            burned_nftokens: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(account.reserve(10_000_000, 2_000_000), BigInt(26_000_000));
+    }
+
+    #[test]
+    fn classic_address_validation() {
+        assert!("rG1QQv2nh2gr7RCZ1P8YYcBUKCCN633jCn"
+            .parse::<ClassicAddress>()
+            .is_ok());
+        assert!("rG1QQv2nh2gr7RCZ1P8YYcBUKCCN633jCm"
+            .parse::<ClassicAddress>()
+            .is_err());
+        assert!(serde_json::from_value::<ClassicAddress>(json!("not an address")).is_err());
+    }
+
+    #[test]
+    fn deserialize_account_objects() {
+        let entries: Vec<LedgerEntry> = serde_json::from_value(json!([
+            {
+                "Account": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn",
+                "BookDirectory": "50AD0A9E54D2B381288D535EB724E4275FFBF41580D28A925D038D7EA4C68000",
+                "BookNode": "0000000000000000",
+                "Flags": 131072,
+                "LedgerEntryType": "Offer",
+                "OwnerNode": "0000000000000000",
+                "Sequence": 8,
+                "TakerGets": "1000000",
+                "TakerPays": {
+                    "currency": "USD",
+                    "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+                    "value": "1"
+                }
+            },
+            {
+                "Account": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn",
+                "Flags": 0,
+                "LedgerEntryType": "Ticket",
+                "OwnerNode": "0000000000000000",
+                "TicketSequence": 3
+            },
+            {
+                "LedgerEntryType": "NFTokenPage"
+            }
+        ]))
+        .unwrap();
+        assert!(matches!(&entries[0], LedgerEntry::Offer(offer) if offer.sequence == 8));
+        assert!(matches!(&entries[1], LedgerEntry::Ticket(ticket) if ticket.ticket_sequence == 3));
+        assert_eq!(entries[2], LedgerEntry::Unknown);
+    }
+
+    #[test]
+    fn api_error_codes() {
+        let res: ErrorResponse = serde_json::from_value(json!({
+            "account": "rG1QQv2nh2gr7RCZ1P8YYcBUKCCN633jCn",
+            "error": "actNotFound",
+            "error_code": 19,
+            "error_message": "Account not found.",
+            "status": "error",
+            "validated": true
+        }))
+        .unwrap();
+        assert_eq!(res.error, Some(ApiErrorCode::ActNotFound));
+        assert_eq!(res.error_code, Some(19));
+        assert_eq!(res.error_message.as_deref(), Some("Account not found."));
+        let code = ApiErrorCode::from("unknownOption");
+        assert_eq!(code, ApiErrorCode::Other("unknownOption".to_owned()));
+        assert_eq!(serde_json::to_value(&code).unwrap(), json!("unknownOption"));
+        assert_eq!(ApiErrorCode::TooBusy.to_string(), "tooBusy");
+    }
+
+    #[test]
+    fn ripple_state_balance() {
+        let entry: LedgerEntry = serde_json::from_value(json!({
+            "LedgerEntryType": "RippleState",
+            "Balance": {
+                "currency": "USD",
+                "issuer": "rrrrrrrrrrrrrrrrrrrrBZbvji",
+                "value": "-10"
+            },
+            "Flags": 393216,
+            "HighLimit": {
+                "currency": "USD",
+                "issuer": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn",
+                "value": "110"
+            },
+            "HighNode": "0000000000000000",
+            "LowLimit": {
+                "currency": "USD",
+                "issuer": "rsA2LpzuawewSBQXkiju3YQTMzW13pAAdW",
+                "value": "0"
+            },
+            "LowNode": "0000000000000000"
+        }))
+        .unwrap();
+        let state = match entry {
+            LedgerEntry::RippleState(state) => state,
+            _ => panic!("expected a RippleState"),
+        };
+        assert_eq!(
+            state.balance_for(&"rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn".to_owned()),
+            "10".parse().unwrap()
+        );
+        assert_eq!(
+            state.balance_for(&"rsA2LpzuawewSBQXkiju3YQTMzW13pAAdW".to_owned()),
+            "-10".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn hash256_round_trip() {
+        let hash = "e08d6e9754025ba2534a78707605e0601f03ace063687a0ca1bddacfcd1698c7";
+        let parsed: Hash256 = serde_json::from_value(json!(hash)).unwrap();
+        assert_eq!(
+            serde_json::to_value(parsed).unwrap(),
+            json!(hash.to_uppercase())
+        );
+        assert!(serde_json::from_value::<Hash256>(json!("e08d")).is_err());
+    }
+
+    #[test]
+    fn hash_validation() {
+        assert!(
+            validate_hash("E08D6E9754025BA2534A78707605E0601F03ACE063687A0CA1BDDACFCD1698C7")
+                .is_ok()
+        );
+        assert!(
+            validate_hash("E08D6E9754025BA2534A78707605E0601F03ACE063687A0CA1BDDACFCD1698C")
+                .is_err()
+        );
+        assert!(
+            validate_hash("G08D6E9754025BA2534A78707605E0601F03ACE063687A0CA1BDDACFCD1698C7")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn display_string() {
+        assert_eq!(
+            CurrencyAmount::xrp(10_500_000).to_display_string(),
+            "10.5 XRP"
+        );
+        assert_eq!(CurrencyAmount::xrp(1).to_display_string(), "0.000001 XRP");
+        assert_eq!(
+            CurrencyAmount::issued_currency(
+                "7072.80".parse().unwrap(),
+                "USD",
+                &"rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned()
+            )
+            .to_display_string(),
+            "7072.8 USD (rvYAf...)"
+        );
+    }
+
+    #[test]
+    fn deserialize_xrp_amount_from_string() {
+        let amount: CurrencyAmount = serde_json::from_value(json!("9977")).unwrap();
+        assert_eq!(amount, CurrencyAmount::xrp(9977));
+    }
+
+    #[test]
+    fn deserialize_xrp_amount_from_number() {
+        let amount: CurrencyAmount = serde_json::from_value(json!(9977)).unwrap();
+        assert_eq!(amount, CurrencyAmount::xrp(9977));
+        assert!(serde_json::from_value::<BigInt>(json!(-1)).is_err());
+    }
+
+    #[test]
+    fn deserialize_issued_currency_amount() {
+        let amount: CurrencyAmount = serde_json::from_value(json!({
+            "currency": "USD",
+            "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+            "value": "7072.8"
+        }))
+        .unwrap();
+        assert_eq!(
+            amount,
+            CurrencyAmount::IssuedCurrency(IssuedCurrencyAmount {
+                value: Decimal::new(70728, 1),
+                currency: "USD".to_owned(),
+                issuer: "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn currency_amount_arithmetic() {
+        let issuer = "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned();
+        let usd = |value| CurrencyAmount::issued_currency(value, "USD", &issuer);
+        assert_eq!(
+            CurrencyAmount::xrp(10) + CurrencyAmount::xrp(2),
+            Ok(CurrencyAmount::xrp(12))
+        );
+        assert_eq!(
+            &CurrencyAmount::xrp(10) - &CurrencyAmount::xrp(2),
+            Ok(CurrencyAmount::xrp(8))
+        );
+        assert_eq!(
+            CurrencyAmount::xrp(u64::MAX) + CurrencyAmount::xrp(1),
+            Err(CurrencyAmountError::Overflow)
+        );
+        assert_eq!(
+            CurrencyAmount::xrp(1) - CurrencyAmount::xrp(2),
+            Err(CurrencyAmountError::Overflow)
+        );
+        // Issued currency values may go negative, e.g. the balance of a trust line.
+        assert_eq!(
+            &usd(Decimal::new(15, 1)) + &usd(Decimal::new(25, 1)),
+            Ok(usd(Decimal::new(40, 1)))
+        );
+        assert_eq!(
+            usd(Decimal::new(1, 0)) - usd(Decimal::new(25, 1)),
+            Ok(usd(Decimal::new(-15, 1)))
+        );
+        assert_eq!(
+            usd(Decimal::new(1, 0)) + CurrencyAmount::xrp(1),
+            Err(CurrencyAmountError::MismatchedAssets)
+        );
+        let eur = CurrencyAmount::issued_currency(Decimal::new(1, 0), "EUR", &issuer);
+        assert_eq!(
+            usd(Decimal::new(1, 0)) + eur,
+            Err(CurrencyAmountError::MismatchedAssets)
+        );
+        let other_issuer = "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned();
+        assert_eq!(
+            usd(Decimal::new(1, 0))
+                + CurrencyAmount::issued_currency(Decimal::new(1, 0), "USD", &other_issuer),
+            Err(CurrencyAmountError::MismatchedAssets)
+        );
+    }
+
+    #[test]
+    fn issued_currency_hex_codes() {
+        let issuer = "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned();
+        let code = currency_name_to_hex("MyToken").unwrap();
+        assert_eq!(code, "4D79546F6B656E00000000000000000000000000");
+        let amount =
+            CurrencyAmount::issued_currency_hex(Decimal::new(1, 0), &code, &issuer).unwrap();
+        assert_eq!(
+            amount,
+            CurrencyAmount::issued_currency(Decimal::new(1, 0), &code, &issuer)
+        );
+        let lowercase = "0158415500000000c1f76ff6ecb0bac600000000";
+        assert!(matches!(
+            CurrencyAmount::issued_currency_hex(Decimal::new(1, 0), lowercase, &issuer),
+            Ok(CurrencyAmount::IssuedCurrency(amount))
+                if amount.currency == lowercase.to_uppercase()
+        ));
+        // Names are not hex codes, and standard codes must not be padded into hex codes.
+        for currency in [
+            "USD",
+            "MyToken",
+            &lowercase[1..],
+            &lowercase.replace('c', "g"),
+        ] {
+            assert!(
+                CurrencyAmount::issued_currency_hex(Decimal::new(1, 0), currency, &issuer).is_err(),
+                "{}",
+                currency
+            );
+        }
+        for name in ["USD", "US", "", "TwentyOneCharacters!!"] {
+            assert!(currency_name_to_hex(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn issued_currency_amount_validation() {
+        let issuer = "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B";
+        let amount = IssuedCurrencyAmount::new("7072.8", "USD", issuer).unwrap();
+        assert_eq!(amount.value, Decimal::new(70728, 1));
+        assert!(IssuedCurrencyAmount::new("1.2345678901234567", "USD", issuer).is_err());
+        assert!(IssuedCurrencyAmount::new("1e", "USD", issuer).is_err());
+        assert!(IssuedCurrencyAmount::new("1", "XRP", issuer).is_err());
+        assert!(IssuedCurrencyAmount::new("1", "US", issuer).is_err());
+        assert!(
+            IssuedCurrencyAmount::new("1", "USD", "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59C").is_err()
+        );
+    }
+
+    #[test]
+    fn deserialize_mpt_amount() {
+        let amount: CurrencyAmount = serde_json::from_value(json!({
+            "mpt_issuance_id": "0000012FFD9EE5DA93AC614B4DB94D7E0FCE415CA51BED47",
+            "value": "100"
+        }))
+        .unwrap();
+        assert_eq!(
+            amount,
+            CurrencyAmount::MPT(MPTAmount {
+                value: "100".to_owned(),
+                mpt_issuance_id: "0000012FFD9EE5DA93AC614B4DB94D7E0FCE415CA51BED47".to_owned(),
+            })
+        );
+        assert!(!amount.is_zero());
+    }
+}
@@ -0,0 +1,260 @@
+use super::{book::BookCurrency, Address, CurrencyAmount, LedgerInfo};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A step in a payment path. Each step is either an account to ripple through, or a currency and
+/// issuer to convert to through the order books.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct PathStep {
+    /// (Optional) If present, this path step represents rippling through the specified address.
+    pub account: Option<Address>,
+    /// (Optional) If present, this path step represents changing currencies through an order book. The currency specified indicates the new currency.
+    pub currency: Option<String>,
+    /// (Optional) If present, this path step represents changing currencies through an order book. The issuer specified indicates the issuer of the new currency.
+    pub issuer: Option<Address>,
+}
+
+/// A set of alternative paths, each a list of path steps.
+pub type PathSet = Vec<Vec<PathStep>>;
+
+/// Used to make ripple_path_find requests, which find paths for a payment in a single request.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct RipplePathFindRequest {
+    /// Unique address of the account that would send funds in a transaction.
+    pub source_account: Address,
+    /// Unique address of the account that would receive funds in a transaction.
+    pub destination_account: Address,
+    /// Currency Amount that the destination account would receive in a transaction.
+    pub destination_amount: CurrencyAmount,
+    /// (Optional) Currency Amount that would be spent in the transaction. Cannot be used with source_currencies.
+    pub send_max: Option<CurrencyAmount>,
+    /// (Optional) Array of currencies that the source account might want to spend. Each entry should be a currency, and its issuer for non-XRP currencies. There cannot be more than 18 source currencies.
+    pub source_currencies: Option<Vec<BookCurrency>>,
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+}
+
+impl RipplePathFindRequest {
+    /// Creates a request that finds paths for a payment from `source_account` delivering
+    /// `destination_amount` to `destination_account`.
+    pub fn new(
+        source_account: &Address,
+        destination_account: &Address,
+        destination_amount: CurrencyAmount,
+    ) -> Self {
+        Self {
+            source_account: source_account.to_owned(),
+            destination_account: destination_account.to_owned(),
+            destination_amount,
+            ..Default::default()
+        }
+    }
+}
+
+/// The paths found by a ripple_path_find request.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct RipplePathFindResponse {
+    /// Array of objects with possible paths to take. If empty, then there are no paths connecting the source and destination accounts.
+    pub alternatives: Vec<PathAlternative>,
+    /// Unique address of the account that would receive a payment transaction.
+    pub destination_account: Address,
+    /// Array of strings representing the currencies that the destination accepts.
+    pub destination_currencies: Vec<String>,
+    /// (May be omitted) If false, this is the result of an incomplete search.
+    pub full_reply: Option<bool>,
+    #[serde(flatten)]
+    pub ledger_info: LedgerInfo,
+}
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum PathFindSubcommand {
+    /// Start sending pathfinding information.
+    Create,
+    /// Stop sending pathfinding information.
+    Close,
+    /// Get the information of the currently-open pathfinding request.
+    Status,
+}
+
+impl Default for PathFindSubcommand {
+    fn default() -> Self {
+        Self::Create
+    }
+}
+
+/// Used to make path_find requests. Only `subcommand` is used by the close and status
+/// subcommands.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct PathFindRequest {
+    /// Use "create" to send the create sub-command.
+    pub subcommand: PathFindSubcommand,
+    /// The unique address of the account to find a path from. (In other words, the account that would be sending a payment.)
+    pub source_account: Option<Address>,
+    /// The unique address of the account to find a path to. (In other words, the account that would receive a payment.)
+    pub destination_account: Option<Address>,
+    /// Currency Amount that the destination account would receive in a transaction.
+    pub destination_amount: Option<CurrencyAmount>,
+    /// (Optional) Currency Amount that would be spent in the transaction.
+    pub send_max: Option<CurrencyAmount>,
+    /// (Optional) Array of arrays of objects, representing payment paths to check. You can use this to keep updated on changes to particular paths you already know about, or to check the overall cost to make a payment along a certain path.
+    pub paths: Option<PathSet>,
+}
+
+impl PathFindRequest {
+    /// Creates a request that starts finding paths for a payment from `source_account` delivering
+    /// `destination_amount` to `destination_account`.
+    pub fn create(
+        source_account: &Address,
+        destination_account: &Address,
+        destination_amount: CurrencyAmount,
+    ) -> Self {
+        Self {
+            subcommand: PathFindSubcommand::Create,
+            source_account: Some(source_account.to_owned()),
+            destination_account: Some(destination_account.to_owned()),
+            destination_amount: Some(destination_amount),
+            ..Default::default()
+        }
+    }
+    /// Creates a request that closes the currently-open pathfinding request.
+    pub fn close() -> Self {
+        Self {
+            subcommand: PathFindSubcommand::Close,
+            ..Default::default()
+        }
+    }
+    /// Creates a request for the status of the currently-open pathfinding request.
+    pub fn status() -> Self {
+        Self {
+            subcommand: PathFindSubcommand::Status,
+            ..Default::default()
+        }
+    }
+}
+
+/// The paths found for a path_find request, returned in the response to the create and status
+/// subcommands and sent as an update whenever the paths change.
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct PathFindResponse {
+    /// (May be omitted) Array of objects with suggested paths to take. If empty, then no paths were found connecting the source and destination accounts.
+    pub alternatives: Option<Vec<PathAlternative>>,
+    /// (May be omitted) Unique address of the account that would receive a transaction.
+    pub destination_account: Option<Address>,
+    /// (May be omitted) Currency Amount the destination would receive in a transaction.
+    pub destination_amount: Option<CurrencyAmount>,
+    /// (May be omitted) Unique address that would send a transaction.
+    pub source_account: Option<Address>,
+    /// (May be omitted) If false, this is the result of an incomplete search. A later reply may have a better path. If true, then this is the best path found. (It is still theoretically possible that a better path could exist, but rippled won't find it.) Until you close the pathfinding request, rippled continues to send updates each time a new ledger closes.
+    pub full_reply: Option<bool>,
+    /// (May be omitted) The value true indicates this reply is in response to a path_find close command.
+    pub closed: Option<bool>,
+    /// (May be omitted) The value true indicates this reply is in response to a path_find status command for which there was no pathfinding request open.
+    pub status: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct PathAlternative {
+    /// Array of arrays of objects defining payment paths.
+    pub paths_computed: PathSet,
+    /// Currency Amount that the source would have to send along this path for the destination to receive the desired amount.
+    pub source_amount: CurrencyAmount,
+    /// (May be omitted) Destination Amount that the destination would receive along this path. Only included if the destination_amount from the request was the "-1" special case.
+    pub destination_amount: Option<CurrencyAmount>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PathStep, RipplePathFindRequest, RipplePathFindResponse};
+    use crate::types::book::BookCurrency;
+    use crate::types::subscribe::SubscriptionEvent;
+    use crate::types::CurrencyAmount;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_path_find_update() {
+        let event: SubscriptionEvent = serde_json::from_value(json!({
+            "type": "path_find",
+            "id": 8,
+            "source_account": "r9cZA1mLK5R5Am25ArfXFmqgNwjZgnfk59",
+            "destination_account": "r9cZA1mLK5R5Am25ArfXFmqgNwjZgnfk59",
+            "destination_amount": "1000000",
+            "full_reply": true,
+            "alternatives": [{
+                "paths_computed": [[
+                    {"currency": "USD", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B"}
+                ]],
+                "source_amount": {
+                    "currency": "USD",
+                    "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+                    "value": "0.5"
+                }
+            }]
+        }))
+        .unwrap();
+        let paths = match event {
+            SubscriptionEvent::PathFind(paths) => paths,
+            _ => panic!("expected a path_find event"),
+        };
+        assert_eq!(paths.full_reply, Some(true));
+        assert_eq!(paths.destination_amount, Some(CurrencyAmount::xrp(1000000)));
+        let alternatives = paths.alternatives.unwrap();
+        assert_eq!(
+            alternatives[0].paths_computed[0][0].currency.as_deref(),
+            Some("USD")
+        );
+    }
+
+    #[test]
+    fn ripple_path_find() {
+        let issuer = "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_owned();
+        let request = RipplePathFindRequest {
+            source_currencies: Some(vec![BookCurrency::issued("USD", &issuer)]),
+            ..RipplePathFindRequest::new(
+                &"r9cZA1mLK5R5Am25ArfXFmqgNwjZgnfk59".to_owned(),
+                &"rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned(),
+                CurrencyAmount::xrp(1000000),
+            )
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "source_account": "r9cZA1mLK5R5Am25ArfXFmqgNwjZgnfk59",
+                "destination_account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+                "destination_amount": "1000000",
+                "source_currencies": [{"currency": "USD", "issuer": issuer}]
+            })
+        );
+        let res: RipplePathFindResponse = serde_json::from_value(json!({
+            "alternatives": [{
+                "paths_canonical": [],
+                "paths_computed": [[
+                    {"currency": "XRP", "type": 16, "type_hex": "0000000000000010"}
+                ]],
+                "source_amount": {"currency": "USD", "issuer": issuer, "value": "0.5"}
+            }],
+            "destination_account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+            "destination_currencies": ["USD", "XRP"],
+            "full_reply": true,
+            "ledger_current_index": 72153921,
+            "status": "success",
+            "validated": false
+        }))
+        .unwrap();
+        assert_eq!(res.destination_currencies, vec!["USD", "XRP"]);
+        assert_eq!(res.ledger_info.ledger_current_index, Some(72153921));
+        assert_eq!(
+            res.alternatives[0].paths_computed,
+            vec![vec![PathStep {
+                currency: Some("XRP".to_owned()),
+                ..Default::default()
+            }]]
+        );
+    }
+}
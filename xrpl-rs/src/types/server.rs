@@ -0,0 +1,162 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct ServerStateRequest {}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct ServerStateResponse {
+    /// A map of various server health information.
+    pub state: ServerState,
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct ServerState {
+    /// The version number of the running rippled version.
+    pub build_version: Option<String>,
+    /// Range expression indicating the sequence numbers of the ledger versions the local rippled has in its database. It is possible to be a disjoint sequence, e.g. "2500-5000,32570-7695432". If the server does not have any complete ledgers (for example, it recently started syncing with the network), this is the string empty.
+    pub complete_ledgers: Option<String>,
+    /// This is the baseline amount of server load used in transaction cost calculations. If the load_factor is equal to the load_base then only the base transaction cost is enforced. If the load_factor is higher than the load_base, then transaction costs are multiplied by the ratio between them.
+    pub load_base: Option<u64>,
+    /// The load factor the server is currently enforcing. The ratio between this value and the load_base determines the multiplier for transaction costs.
+    pub load_factor: Option<u64>,
+    /// A string indicating to what extent the server is participating in the network.
+    pub server_state: Option<String>,
+    /// (May be omitted) Information about the most recent fully-validated ledger. If the most recent validated ledger is not available, the response omits this field and includes closed_ledger instead.
+    pub validated_ledger: Option<ServerStateLedger>,
+    /// (May be omitted) Information on the most recently closed ledger that has not been validated by consensus. If the most recently validated ledger is available, the response omits this field and includes validated_ledger instead.
+    pub closed_ledger: Option<ServerStateLedger>,
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct ServerStateLedger {
+    /// Base fee, in drops of XRP, for propagating a transaction to the network.
+    pub base_fee: u64,
+    /// Time this ledger was closed, in seconds since the Ripple Epoch.
+    pub close_time: u64,
+    /// Unique hash of this ledger version, as hexadecimal.
+    pub hash: String,
+    /// The minimum account reserve, as of the most recent validated ledger version, in drops of XRP.
+    pub reserve_base: u64,
+    /// The owner reserve for each item an account owns, as of the most recent validated ledger version, in drops of XRP.
+    pub reserve_inc: u64,
+    /// The ledger index of the latest validated ledger.
+    pub seq: u32,
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct ServerDefinitionsRequest {}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct ServerInfoRequest {}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct ServerInfoResponse {
+    /// A map of various server health information.
+    pub info: ServerInfo,
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct ServerInfo {
+    /// The version number of the running rippled server.
+    pub build_version: Option<String>,
+    /// Range expression indicating the sequence numbers of the ledger versions the local rippled has in its database. This may be a disjoint sequence such as 24900901-24900984,24901116-24901158. If the server does not have any complete ledgers (for example, it recently started syncing with the network), this is the string empty.
+    pub complete_ledgers: Option<String>,
+    /// The multiplier to the transaction cost the server is currently enforcing. For example, at 1000 load factor and a reference transaction cost of 10 drops of XRP, the load-scaled transaction cost is 10,000 drops (0.01 XRP). The load factor is determined by the highest of the individual server's load factor, the cluster's load factor, the open ledger cost and the overall network's load factor.
+    pub load_factor: Option<Decimal>,
+    /// A string indicating to what extent the server is participating in the network.
+    pub server_state: Option<String>,
+    /// (May be omitted) Information about the most recent fully-validated ledger. If the most recent validated ledger is not available, the response omits this field and includes closed_ledger instead.
+    pub validated_ledger: Option<ServerInfoLedger>,
+    /// (May be omitted) Information on the most recently closed ledger that has not been validated by consensus. If the most recently validated ledger is available, the response omits this field and includes validated_ledger instead.
+    pub closed_ledger: Option<ServerInfoLedger>,
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct ServerInfoLedger {
+    /// The time since the ledger was closed, in seconds.
+    pub age: Option<u64>,
+    /// Base fee, in XRP. This may be represented in scientific notation such as 1e-05 for 0.00001.
+    pub base_fee_xrp: Decimal,
+    /// Unique hash for the ledger, as hexadecimal.
+    pub hash: Option<String>,
+    /// Minimum amount of XRP (not drops) necessary for every account to keep in reserve.
+    pub reserve_base_xrp: Decimal,
+    /// Amount of XRP (not drops) added to the account reserve for each object an account owns in the ledger.
+    pub reserve_inc_xrp: Decimal,
+    /// The ledger index of the latest validated ledger.
+    pub seq: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ServerInfoResponse;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    #[test]
+    fn server_definitions_response() {
+        let definitions: serde_xrpl::definitions::Definitions = serde_json::from_value(json!({
+            "FIELDS": [
+                ["Sequence", {
+                    "nth": 4,
+                    "isVLEncoded": false,
+                    "isSerialized": true,
+                    "isSigningField": true,
+                    "type": "UInt32"
+                }]
+            ],
+            "LEDGER_ENTRY_TYPES": {},
+            "TRANSACTION_RESULTS": { "tesSUCCESS": 0 },
+            "TRANSACTION_TYPES": { "Payment": 0 },
+            "TYPES": { "UInt32": 2 },
+            "hash": "7B5A7B9B0C4D5E6F7B5A7B9B0C4D5E6F7B5A7B9B0C4D5E6F7B5A7B9B0C4D5E6F",
+            "status": "success"
+        }))
+        .unwrap();
+        assert!(definitions.hash.is_some());
+        let bytes = serde_xrpl::ser::to_bytes_with_definitions(
+            &json!({ "Sequence": 5 }),
+            None,
+            &definitions,
+        )
+        .unwrap();
+        assert_eq!(bytes, [0x24, 0, 0, 0, 5]);
+    }
+
+    #[test]
+    fn deserialize_server_info_xrp_values() {
+        let res: ServerInfoResponse = serde_json::from_value(json!({
+            "info": {
+                "build_version": "1.9.4",
+                "complete_ledgers": "32570-75801747",
+                "load_factor": 1,
+                "server_state": "full",
+                "validated_ledger": {
+                    "age": 2,
+                    "base_fee_xrp": 1e-05,
+                    "hash": "C3CA4D3EE4F1D8F4B0F1A76FE13EB8C4A8DB5A5A93D4D39548F1C8B2F10F8A0E",
+                    "reserve_base_xrp": 10,
+                    "reserve_inc_xrp": 2,
+                    "seq": 75801747
+                }
+            }
+        }))
+        .unwrap();
+        let ledger = res.info.validated_ledger.unwrap();
+        assert_eq!(ledger.base_fee_xrp, Decimal::new(1, 5));
+        assert_eq!(ledger.reserve_base_xrp, Decimal::new(10, 0));
+        assert_eq!(ledger.reserve_inc_xrp, Decimal::new(2, 0));
+        assert_eq!(res.info.load_factor, Some(Decimal::new(1, 0)));
+    }
+}
@@ -1,5 +1,7 @@
+use super::BigInt;
 use crate::transaction::types::Transaction;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use serde_with::skip_serializing_none;
 
 #[skip_serializing_none]
@@ -53,3 +55,318 @@ pub struct SubmitResponse {
     /// JSON specification of the complete transaction as signed, including any fields that were automatically filled in
     pub tx_json: Option<Transaction>,
 }
+
+impl SubmitResponse {
+    /// Returns the sequence the transaction was submitted with, which the server chooses when it
+    /// signs the transaction. After a server signed submission, setting a local `Wallet`'s
+    /// sequence to one more than this keeps it in step with the account. Transactions using a
+    /// Ticket have a sequence of 0, for which `None` is returned.
+    pub fn assigned_sequence(&self) -> Option<u32> {
+        self.tx_json
+            .as_ref()
+            .map(|tx| tx.sequence)
+            .filter(|sequence| *sequence != 0)
+    }
+    /// Returns the fee the transaction was submitted with, which the server chooses when it signs
+    /// the transaction.
+    pub fn assigned_fee(&self) -> Option<BigInt> {
+        self.tx_json.as_ref().map(|tx| tx.fee.clone())
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct SimulateRequest {
+    /// (Optional) Transaction definition in JSON format. Cannot be used with tx_blob.
+    pub tx_json: Option<Transaction>,
+    /// (Optional) Hex representation of the transaction to simulate. Cannot be used with tx_json.
+    pub tx_blob: Option<String>,
+    /// (Optional, defaults to false) If true, return the transaction and metadata as binary serialized to hexadecimal strings.
+    pub binary: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct SimulateResponse {
+    /// Text result code indicating the result of the simulated transaction, for example tesSUCCESS.
+    pub engine_result: String,
+    /// Numeric code indicating the result of the simulated transaction, directly correlated to engine_result.
+    pub engine_result_code: i64,
+    /// (May be omitted) Human-readable explanation of the simulated transaction's result.
+    pub engine_result_message: Option<String>,
+    /// The metadata the transaction would produce if applied, describing the resulting balance and ledger object changes.
+    pub meta: Option<Value>,
+    /// JSON specification of the simulated transaction, including any fields that were automatically filled in.
+    pub tx_json: Option<Transaction>,
+    /// The ledger index of the ledger the transaction was simulated against.
+    pub ledger_index: Option<u32>,
+}
+
+/// The category of a transaction engine result, determined by its prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineResultCategory {
+    /// tes: The transaction succeeded and is included in the ledger once validated.
+    Success,
+    /// tec: The transaction failed but is included in the ledger to claim the transaction cost. It is final once validated and should not be retried as is.
+    ClaimedFee,
+    /// tef: The transaction cannot be applied to the server's current (in-progress) ledger or any later one, for example because it has already been applied.
+    Failure,
+    /// tel: The rippled server had an error due to local conditions, such as high load. A different server, or a later attempt, may succeed.
+    LocalError,
+    /// tem: The transaction was not valid, due to improper syntax, conflicting options, a bad signature, or something else.
+    Malformed,
+    /// ter: The transaction could not be applied, but it could apply successfully in a future ledger.
+    Retry,
+    /// The engine result did not have a recognised prefix.
+    Unknown,
+}
+
+macro_rules! engine_results {
+    ($($variant: ident => $code: expr),* $(,)?) => {
+        /// A transaction engine result code, as returned in `engine_result` and `TransactionResult`.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum EngineResult {
+            $($variant,)*
+            /// A result code that does not have a dedicated variant.
+            Other(String),
+        }
+
+        impl EngineResult {
+            /// Returns the result code as it is represented by rippled, e.g. tecKILLED.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $code,)*
+                    Self::Other(code) => code,
+                }
+            }
+        }
+
+        impl From<&str> for EngineResult {
+            fn from(code: &str) -> Self {
+                match code {
+                    $($code => Self::$variant,)*
+                    _ => Self::Other(code.to_owned()),
+                }
+            }
+        }
+    };
+}
+
+engine_results!(
+    TesSuccess => "tesSUCCESS",
+    TecClaim => "tecCLAIM",
+    TecPathPartial => "tecPATH_PARTIAL",
+    TecUnfundedAdd => "tecUNFUNDED_ADD",
+    TecUnfundedOffer => "tecUNFUNDED_OFFER",
+    TecUnfundedPayment => "tecUNFUNDED_PAYMENT",
+    TecFailedProcessing => "tecFAILED_PROCESSING",
+    TecDirFull => "tecDIR_FULL",
+    TecInsufReserveLine => "tecINSUF_RESERVE_LINE",
+    TecInsufReserveOffer => "tecINSUF_RESERVE_OFFER",
+    TecNoDst => "tecNO_DST",
+    TecNoDstInsufXrp => "tecNO_DST_INSUF_XRP",
+    TecNoLineInsufReserve => "tecNO_LINE_INSUF_RESERVE",
+    TecNoLineRedundant => "tecNO_LINE_REDUNDANT",
+    TecPathDry => "tecPATH_DRY",
+    TecUnfunded => "tecUNFUNDED",
+    TecNoAlternativeKey => "tecNO_ALTERNATIVE_KEY",
+    TecNoRegularKey => "tecNO_REGULAR_KEY",
+    TecOwners => "tecOWNERS",
+    TecNoIssuer => "tecNO_ISSUER",
+    TecNoAuth => "tecNO_AUTH",
+    TecNoLine => "tecNO_LINE",
+    TecInsuffFee => "tecINSUFF_FEE",
+    TecFrozen => "tecFROZEN",
+    TecNoTarget => "tecNO_TARGET",
+    TecNoPermission => "tecNO_PERMISSION",
+    TecNoEntry => "tecNO_ENTRY",
+    TecInsufficientReserve => "tecINSUFFICIENT_RESERVE",
+    TecNeedMasterKey => "tecNEED_MASTER_KEY",
+    TecDstTagNeeded => "tecDST_TAG_NEEDED",
+    TecInternal => "tecINTERNAL",
+    TecOversize => "tecOVERSIZE",
+    TecCryptoconditionError => "tecCRYPTOCONDITION_ERROR",
+    TecInvariantFailed => "tecINVARIANT_FAILED",
+    TecExpired => "tecEXPIRED",
+    TecDuplicate => "tecDUPLICATE",
+    TecKilled => "tecKILLED",
+    TecHasObligations => "tecHAS_OBLIGATIONS",
+    TecTooSoon => "tecTOO_SOON",
+    TecMaxSequenceReached => "tecMAX_SEQUENCE_REACHED",
+    TecNoSuitableNftokenPage => "tecNO_SUITABLE_NFTOKEN_PAGE",
+    TecNftokenBuySellMismatch => "tecNFTOKEN_BUY_SELL_MISMATCH",
+    TecNftokenOfferTypeMismatch => "tecNFTOKEN_OFFER_TYPE_MISMATCH",
+    TecCantAcceptOwnNftokenOffer => "tecCANT_ACCEPT_OWN_NFTOKEN_OFFER",
+    TecInsufficientFunds => "tecINSUFFICIENT_FUNDS",
+    TecObjectNotFound => "tecOBJECT_NOT_FOUND",
+    TecInsufficientPayment => "tecINSUFFICIENT_PAYMENT",
+    TefFailure => "tefFAILURE",
+    TefAlready => "tefALREADY",
+    TefBadAuth => "tefBAD_AUTH",
+    TefBadLedger => "tefBAD_LEDGER",
+    TefPastSeq => "tefPAST_SEQ",
+    TefMaxLedger => "tefMAX_LEDGER",
+    TefMasterDisabled => "tefMASTER_DISABLED",
+    TefBadSignature => "tefBAD_SIGNATURE",
+    TefBadQuorum => "tefBAD_QUORUM",
+    TefNoTicket => "tefNO_TICKET",
+    TelLocalError => "telLOCAL_ERROR",
+    TelInsufFeeP => "telINSUF_FEE_P",
+    TelCanNotQueue => "telCAN_NOT_QUEUE",
+    TelCanNotQueueFee => "telCAN_NOT_QUEUE_FEE",
+    TelCanNotQueueFull => "telCAN_NOT_QUEUE_FULL",
+    TemMalformed => "temMALFORMED",
+    TemBadAmount => "temBAD_AMOUNT",
+    TemBadCurrency => "temBAD_CURRENCY",
+    TemBadFee => "temBAD_FEE",
+    TemBadOffer => "temBAD_OFFER",
+    TemBadSequence => "temBAD_SEQUENCE",
+    TemBadSignature => "temBAD_SIGNATURE",
+    TemDstIsSrc => "temDST_IS_SRC",
+    TemDstNeeded => "temDST_NEEDED",
+    TemInvalid => "temINVALID",
+    TemInvalidFlag => "temINVALID_FLAG",
+    TemRedundant => "temREDUNDANT",
+    TemDisabled => "temDISABLED",
+    TerRetry => "terRETRY",
+    TerInsufFeeB => "terINSUF_FEE_B",
+    TerNoAccount => "terNO_ACCOUNT",
+    TerNoAuth => "terNO_AUTH",
+    TerNoLine => "terNO_LINE",
+    TerPreSeq => "terPRE_SEQ",
+    TerQueued => "terQUEUED",
+);
+
+impl EngineResult {
+    /// Returns the category of the result, which determines whether the transaction may be
+    /// included in a ledger and whether it is worth retrying.
+    pub fn category(&self) -> EngineResultCategory {
+        let code = self.as_str();
+        if code.starts_with("tes") {
+            EngineResultCategory::Success
+        } else if code.starts_with("tec") {
+            EngineResultCategory::ClaimedFee
+        } else if code.starts_with("tef") {
+            EngineResultCategory::Failure
+        } else if code.starts_with("tel") {
+            EngineResultCategory::LocalError
+        } else if code.starts_with("tem") {
+            EngineResultCategory::Malformed
+        } else if code.starts_with("ter") {
+            EngineResultCategory::Retry
+        } else {
+            EngineResultCategory::Unknown
+        }
+    }
+}
+
+impl std::fmt::Display for EngineResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for EngineResult {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for EngineResult {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        Ok(Self::from(String::deserialize(deserializer)?.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EngineResult, EngineResultCategory, SubmitResponse};
+    use crate::types::BigInt;
+    use serde_json::json;
+
+    #[test]
+    fn assigned_sequence_and_fee() {
+        let response: SubmitResponse = serde_json::from_value(json!({
+            "engine_result": "tesSUCCESS",
+            "tx_json": {
+                "Account": "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh",
+                "Amount": "1000000",
+                "Destination": "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw",
+                "Fee": "12",
+                "Flags": 0,
+                "LastLedgerSequence": 0,
+                "Sequence": 42,
+                "SigningPubKey": "0330E7FC9D56BB25D6893BA3F317AE5BCF33B3291BD63DB32654A313222F7FD020",
+                "TransactionType": "Payment"
+            }
+        }))
+        .unwrap();
+        assert_eq!(response.assigned_sequence(), Some(42));
+        assert_eq!(response.assigned_fee(), Some(BigInt(12)));
+        assert_eq!(SubmitResponse::default().assigned_sequence(), None);
+    }
+
+    #[test]
+    fn engine_result_category() {
+        let cases = [
+            (
+                "tesSUCCESS",
+                EngineResult::TesSuccess,
+                EngineResultCategory::Success,
+            ),
+            (
+                "tecKILLED",
+                EngineResult::TecKilled,
+                EngineResultCategory::ClaimedFee,
+            ),
+            (
+                "tecEXPIRED",
+                EngineResult::TecExpired,
+                EngineResultCategory::ClaimedFee,
+            ),
+            (
+                "tecUNFUNDED_OFFER",
+                EngineResult::TecUnfundedOffer,
+                EngineResultCategory::ClaimedFee,
+            ),
+            (
+                "tefPAST_SEQ",
+                EngineResult::TefPastSeq,
+                EngineResultCategory::Failure,
+            ),
+            (
+                "telCAN_NOT_QUEUE_FEE",
+                EngineResult::TelCanNotQueueFee,
+                EngineResultCategory::LocalError,
+            ),
+            (
+                "temBAD_FEE",
+                EngineResult::TemBadFee,
+                EngineResultCategory::Malformed,
+            ),
+            (
+                "terQUEUED",
+                EngineResult::TerQueued,
+                EngineResultCategory::Retry,
+            ),
+        ];
+        for (code, result, category) in cases {
+            assert_eq!(EngineResult::from(code), result);
+            assert_eq!(result.as_str(), code);
+            assert_eq!(result.category(), category);
+        }
+        let other = EngineResult::from("tecSOMETHING_NEW");
+        assert_eq!(other, EngineResult::Other("tecSOMETHING_NEW".to_owned()));
+        assert_eq!(other.category(), EngineResultCategory::ClaimedFee);
+        assert_eq!(
+            EngineResult::from("xyzUNKNOWN").category(),
+            EngineResultCategory::Unknown
+        );
+    }
+}
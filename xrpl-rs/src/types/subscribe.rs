@@ -1,14 +1,60 @@
-use super::Address;
+use super::{
+    book::BookCurrency, path::PathFindResponse, tx::TransactionMetadata, Address, Integer,
+};
+use crate::transaction::types::Transaction;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
+/// Used to make subscribe requests.
 #[skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug)]
-pub enum SubscribeRequest {
-    #[serde(rename = "accounts")]
-    Accounts(Vec<Address>),
-    #[serde(rename = "streams")]
-    Streams(Vec<String>),
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubscribeRequest {
+    /// (Optional) Array of string names of generic streams to subscribe to, such as ledger or transactions.
+    pub streams: Option<Vec<String>>,
+    /// (Optional) Array with the unique addresses of accounts to monitor for validated transactions. The server sends a notification for any transaction that affects at least one of these accounts.
+    pub accounts: Option<Vec<Address>>,
+    /// (Optional) Like accounts, but include transactions that are not yet finalized.
+    pub accounts_proposed: Option<Vec<Address>>,
+    /// (Optional) Array of objects defining order books to monitor for updates.
+    pub books: Option<Vec<SubscribeBook>>,
+    /// (Optional for Websocket; Required otherwise) URL where the server sends a JSON-RPC callbacks for each event. Admin-only.
+    pub url: Option<String>,
+    /// (Optional) Username to provide for basic authentication at the callback URL.
+    pub url_username: Option<String>,
+    /// (Optional) Password to provide for basic authentication at the callback URL.
+    pub url_password: Option<String>,
+}
+
+impl SubscribeRequest {
+    /// Creates a request subscribing to the provided streams.
+    pub fn streams(streams: Vec<String>) -> Self {
+        Self {
+            streams: Some(streams),
+            ..Default::default()
+        }
+    }
+    /// Creates a request subscribing to validated transactions affecting the provided accounts.
+    pub fn accounts(accounts: Vec<Address>) -> Self {
+        Self {
+            accounts: Some(accounts),
+            ..Default::default()
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubscribeBook {
+    /// Specification of which currency the account taking the Offer would receive.
+    pub taker_gets: BookCurrency,
+    /// Specification of which currency the account taking the Offer would pay.
+    pub taker_pays: BookCurrency,
+    /// Unique account address to use as a perspective for viewing offers.
+    pub taker: Address,
+    /// (Optional) If true, return the current state of the order book once when you subscribe before sending updates. The default is false.
+    pub snapshot: Option<bool>,
+    /// (Optional) If true, return both sides of the order book. The default is false.
+    pub both: Option<bool>,
 }
 
 #[skip_serializing_none]
@@ -17,6 +63,14 @@ pub enum SubscribeRequest {
 pub enum SubscriptionEvent {
     #[serde(rename = "ledgerClosed")]
     LedgerClosed(LedgerClosed),
+    #[serde(rename = "transaction")]
+    Transaction(TransactionEvent),
+    #[serde(rename = "validationReceived")]
+    Validation(ValidationEvent),
+    #[serde(rename = "manifestReceived")]
+    Manifest(ManifestEvent),
+    #[serde(rename = "path_find")]
+    PathFind(PathFindResponse),
 }
 
 #[skip_serializing_none]
@@ -24,4 +78,91 @@ pub enum SubscriptionEvent {
 pub struct LedgerClosed {
     /// The identifying hash of the ledger version that was closed.
     pub ledger_hash: String,
+    /// The ledger index of the ledger that was closed.
+    pub ledger_index: u32,
+    /// The time this ledger was closed, in seconds since the Ripple Epoch.
+    pub ledger_time: Option<u64>,
+    /// The reference transaction cost as of this ledger version, in drops of XRP.
+    pub fee_base: Option<u64>,
+    /// The minimum reserve, in drops of XRP, that is required for an account.
+    pub reserve_base: Option<u64>,
+    /// The increase in account reserve that is added for each item the account owns, in drops of XRP.
+    pub reserve_inc: Option<u64>,
+    /// Number of new transactions included in this ledger version.
+    pub txn_count: Option<u32>,
+    /// (May be omitted) Range of ledgers that the server has available.
+    pub validated_ledgers: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TransactionEvent {
+    /// String Transaction result code.
+    pub engine_result: Option<String>,
+    /// Numeric transaction response code, if applicable.
+    pub engine_result_code: Option<i64>,
+    /// Human-readable explanation for the transaction response.
+    pub engine_result_message: Option<String>,
+    /// (Omitted for proposed transactions) The identifying hash of the ledger version that includes this transaction.
+    pub ledger_hash: Option<String>,
+    /// (Omitted for proposed transactions) The ledger index of the ledger version that includes this transaction.
+    pub ledger_index: Option<u32>,
+    /// (Omitted for validated transactions) The ledger index of the current in-progress ledger version for which this transaction is currently proposed.
+    pub ledger_current_index: Option<u32>,
+    /// (Omitted for proposed transactions) The transaction metadata, which shows the exact outcome of the transaction in detail.
+    pub meta: Option<TransactionMetadata>,
+    /// The definition of the transaction in JSON format.
+    pub transaction: Transaction,
+    /// If true, this transaction is included in a validated ledger and its outcome is final.
+    pub validated: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ValidationEvent {
+    /// The public key from the key-pair that the validator used to sign the message, in the XRP Ledger's base58 format.
+    pub validation_public_key: String,
+    /// The identifying hash of the proposed ledger is being validated.
+    pub ledger_hash: String,
+    /// The Ledger Index of the proposed ledger.
+    pub ledger_index: Integer,
+    /// If true, this is a full validation. Otherwise, this is a partial validation.
+    pub full: bool,
+    /// Bit-map of boolean flags included with this validation message.
+    pub flags: u32,
+    /// The time when the validation message was signed, in seconds since the Ripple Epoch.
+    pub signing_time: u64,
+    /// The signature that the validator used to sign its vote for this ledger.
+    pub signature: Option<String>,
+    /// (May be omitted) The validator's master public key, if the validator is using a validator token, in the XRP Ledger's base58 format.
+    pub master_key: Option<String>,
+    /// (May be omitted) The unscaled transaction cost (reference_fee value) this server wants to set by Fee Voting.
+    pub base_fee: Option<u64>,
+    /// (May be omitted) The minimum reserve requirement (account_reserve value) this validator wants to set by Fee Voting.
+    pub reserve_base: Option<u64>,
+    /// (May be omitted) The increment in the reserve requirement (owner_reserve value) this validator wants to set by Fee Voting.
+    pub reserve_inc: Option<u64>,
+    /// (May be omitted) The local load-scaled transaction cost this validator is currently enforcing, in fee units.
+    pub load_fee: Option<u64>,
+    /// (May be omitted) The amendments this server wants to be added to the protocol.
+    pub amendments: Option<Vec<String>>,
+    /// (May be omitted) The hash of the latest ledger this validator has fully validated.
+    pub validated_hash: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ManifestEvent {
+    /// The master public key of the validator, in the XRP Ledger's base58 format.
+    pub master_key: String,
+    /// The signature of this manifest data from the master key pair.
+    pub master_signature: String,
+    /// The sequence number of this manifest. This number increases whenever the validator operator updates the validator's token to rotate ephemeral keys or change settings.
+    pub seq: u32,
+    /// The signature of this manifest data from the ephemeral key pair.
+    pub signature: String,
+    /// The ephemeral public key of the validator, in the XRP Ledger's base58 format.
+    pub signing_key: String,
+    /// (May be omitted) The domain name the validator operator has associated with this manifest.
+    pub domain: Option<String>,
 }
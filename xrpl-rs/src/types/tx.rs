@@ -1,15 +1,31 @@
+use super::{submit::EngineResult, CurrencyAmount, Hash256};
 use crate::transaction::types::Transaction;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_with::skip_serializing_none;
+use std::collections::HashSet;
 
 #[skip_serializing_none]
 #[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct TxRequest {
     /// The 256-bit hash of the transaction, as hex.
-    pub transaction: String,
+    pub transaction: Hash256,
     /// (Optional) If true, return transaction data and metadata as binary serialized to hexadecimal strings. If false, return transaction data and metadata as JSON. The default is false.
     pub binary: Option<bool>,
+    /// (Optional) Use this with max_ledger to specify a range of up to 1000 ledger indexes, starting with this ledger (inclusive). If the server cannot find the transaction, it confirms whether it was able to search all the ledgers in this range.
+    pub min_ledger: Option<u32>,
+    /// (Optional) Use this with min_ledger to specify a range of up to 1000 ledger indexes, ending with this ledger (inclusive). If the server cannot find the transaction, it confirms whether it was able to search all the ledgers in the requested range.
+    pub max_ledger: Option<u32>,
+}
+
+impl TxRequest {
+    /// Creates a request for the transaction with the provided hash.
+    pub fn new(transaction: &str) -> Result<Self, crate::Error> {
+        Ok(Self {
+            transaction: transaction.parse()?,
+            ..Default::default()
+        })
+    }
 }
 
 #[skip_serializing_none]
@@ -19,4 +35,118 @@ pub struct TxResponse {
     pub hash: String,
     /// Transaction metadata, which describes the results of the transaction.
     pub meta: Option<Value>,
-}
\ No newline at end of file
+    /// The ledger index of the ledger that includes this transaction.
+    pub ledger_index: Option<u32>,
+    /// If true, this data comes from a validated ledger version; if omitted or set to false, this data is not final.
+    pub validated: Option<bool>,
+    /// (May be omitted) When the transaction is not found and min_ledger and max_ledger were provided, whether the server searched every ledger in the range.
+    pub searched_all: Option<bool>,
+}
+
+/// The final outcome of a submitted transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The transaction is in a validated ledger with this result, which is final.
+    Validated(EngineResult),
+    /// The transaction is in no validated ledger up to its LastLedgerSequence, so it can never
+    /// be validated.
+    Expired,
+    /// The outcome is not known yet, either because the transaction is not validated yet or
+    /// because the server could not search every ledger up to the LastLedgerSequence.
+    Pending,
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct TransactionMetadata {
+    /// List of ledger objects that were created, deleted, or modified by this transaction.
+    pub affected_nodes: Vec<Value>,
+    /// The transaction's position within the ledger that included it.
+    pub transaction_index: u32,
+    /// A result code indicating whether the transaction succeeded or how it failed.
+    pub transaction_result: String,
+    /// (Omitted for non-Payment transactions) The Currency Amount actually received by the Destination account.
+    #[serde(rename = "delivered_amount")]
+    pub delivered_amount: Option<CurrencyAmount>,
+}
+
+/// Returns the NFTokenID of the token minted by an NFTokenMint transaction from its metadata.
+///
+/// Uses the `nftoken_id` field when the server provides it, otherwise compares the tokens held
+/// by the NFTokenPage nodes before and after the transaction. Tokens that moved between pages
+/// when a page was split appear on both sides, leaving only the newly minted token.
+pub fn minted_nftoken_id(meta: &Value) -> Option<Hash256> {
+    if let Some(id) = meta["nftoken_id"].as_str() {
+        return id.parse().ok();
+    }
+    let mut previous = HashSet::new();
+    let mut current = Vec::new();
+    for node in meta["AffectedNodes"].as_array()? {
+        let (node, fields) = if let Some(node) = node.get("CreatedNode") {
+            (node, &node["NewFields"])
+        } else if let Some(node) = node.get("ModifiedNode") {
+            (node, &node["FinalFields"])
+        } else {
+            continue;
+        };
+        if node["LedgerEntryType"] != "NFTokenPage" {
+            continue;
+        }
+        previous.extend(nftoken_ids(&node["PreviousFields"]));
+        current.extend(nftoken_ids(fields));
+    }
+    current
+        .into_iter()
+        .find(|id| !previous.contains(id))
+        .and_then(|id| id.parse().ok())
+}
+
+fn nftoken_ids(fields: &Value) -> impl Iterator<Item = &str> {
+    fields["NFTokens"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|token| token["NFToken"]["NFTokenID"].as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::minted_nftoken_id;
+    use crate::types::Hash256;
+    use serde_json::json;
+
+    #[test]
+    fn minted_nftoken_id_from_metadata() {
+        let id = "00080000B4F4AFC5FBCBD76873F18006173D2193467D3EE70000099B00000001";
+        let expected: Option<Hash256> = Some(id.parse().unwrap());
+        assert_eq!(
+            minted_nftoken_id(&json!({ "AffectedNodes": [], "nftoken_id": id })),
+            expected
+        );
+        let token = |id: &str| json!({ "NFToken": { "NFTokenID": id, "URI": "" } });
+        let moved = "00080000B4F4AFC5FBCBD76873F18006173D2193467D3EE70000099B00000000";
+        // A mint that split a full page: the existing token moves to the created page.
+        let meta = json!({
+            "AffectedNodes": [
+                { "ModifiedNode": {
+                    "LedgerEntryType": "AccountRoot",
+                    "FinalFields": { "MintedNFTokens": 2 },
+                    "PreviousFields": { "MintedNFTokens": 1 }
+                } },
+                { "CreatedNode": {
+                    "LedgerEntryType": "NFTokenPage",
+                    "NewFields": { "NFTokens": [token(moved)] }
+                } },
+                { "ModifiedNode": {
+                    "LedgerEntryType": "NFTokenPage",
+                    "FinalFields": { "NFTokens": [token(id)] },
+                    "PreviousFields": { "NFTokens": [token(moved)] }
+                } }
+            ],
+            "TransactionResult": "tesSUCCESS"
+        });
+        assert_eq!(minted_nftoken_id(&meta), expected);
+        assert_eq!(minted_nftoken_id(&json!({ "AffectedNodes": [] })), None);
+    }
+}
@@ -1 +1,225 @@
-pub mod testnet;
\ No newline at end of file
+use ripemd::Digest;
+use serde_xrpl::hash_prefixes;
+use sha2::Sha512;
+
+use crate::types::ledger::{BinaryTransaction, Ledger};
+
+pub mod testnet;
+
+/// The Ripple Epoch, 2000-01-01 00:00:00 UTC, in seconds since the Unix epoch.
+pub const RIPPLE_EPOCH: i64 = 946_684_800;
+
+/// Converts a time in seconds since the Ripple Epoch, as used in ledger close times and
+/// transaction expirations, to seconds since the Unix epoch.
+pub fn ripple_time_to_unix(ripple_time: u64) -> i64 {
+    ripple_time as i64 + RIPPLE_EPOCH
+}
+
+/// Converts a time in seconds since the Unix epoch to seconds since the Ripple Epoch. Returns
+/// `None` for times before the Ripple Epoch, which the XRPL can't represent.
+pub fn unix_time_to_ripple(unix_time: i64) -> Option<u64> {
+    u64::try_from(unix_time - RIPPLE_EPOCH).ok()
+}
+
+/// Recomputes the root of the transaction tree from the ledger's transactions and checks it
+/// against the `transaction_hash` in the ledger header.
+///
+/// This lets a client check that a server returned the complete and unaltered set of
+/// transactions for a ledger it trusts the header of. Returns false if the header has no
+/// `transaction_hash` or a transaction is not valid hex.
+pub fn verify_transaction_hash(ledger: &Ledger, transactions: &[BinaryTransaction]) -> bool {
+    let expected = match ledger
+        .transaction_hash
+        .as_ref()
+        .and_then(|h| hex::decode(h).ok())
+    {
+        Some(expected) => expected,
+        None => return false,
+    };
+    let mut leaves = Vec::with_capacity(transactions.len());
+    for transaction in transactions {
+        match (
+            hex::decode(&transaction.tx_blob),
+            hex::decode(&transaction.meta),
+        ) {
+            (Ok(tx_blob), Ok(meta)) => leaves.push(transaction_leaf(&tx_blob, &meta)),
+            _ => return false,
+        }
+    }
+    let root = if leaves.is_empty() {
+        [0u8; 32]
+    } else {
+        inner_node_hash(&leaves, 0)
+    };
+    root.to_vec() == expected
+}
+
+/// Returns the key (the transaction id) and hash of a transaction tree leaf.
+fn transaction_leaf(tx_blob: &[u8], meta: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let id = sha512_half(&[&hash_prefixes::TRANSACTION_ID, tx_blob]);
+    let hash = sha512_half(&[
+        &hash_prefixes::TRANSACTION,
+        &serde_xrpl::utils::encode_variable_length(tx_blob.len()),
+        tx_blob,
+        &serde_xrpl::utils::encode_variable_length(meta.len()),
+        meta,
+        &id,
+    ]);
+    (id, hash)
+}
+
+/// Hashes an inner node whose children are selected by the nibble of each leaf key at `depth`.
+fn inner_node_hash(leaves: &[([u8; 32], [u8; 32])], depth: usize) -> [u8; 32] {
+    let mut children = vec![[0u8; 32]; 16];
+    for (branch, child) in children.iter_mut().enumerate() {
+        let branch_leaves: Vec<([u8; 32], [u8; 32])> = leaves
+            .iter()
+            .filter(|(key, _)| nibble(key, depth) == branch)
+            .cloned()
+            .collect();
+        *child = match branch_leaves.len() {
+            0 => [0u8; 32],
+            1 => branch_leaves[0].1,
+            _ => inner_node_hash(&branch_leaves, depth + 1),
+        };
+    }
+    let mut parts: Vec<&[u8]> = vec![&hash_prefixes::INNER_NODE];
+    parts.extend(children.iter().map(|c| &c[..]));
+    sha512_half(&parts)
+}
+
+fn nibble(key: &[u8; 32], depth: usize) -> usize {
+    let byte = key[depth / 2];
+    (if depth % 2 == 0 {
+        byte >> 4
+    } else {
+        byte & 0x0f
+    }) as usize
+}
+
+fn sha512_half(parts: &[&[u8]]) -> [u8; 32] {
+    let mut h = Sha512::new();
+    for part in parts {
+        h.update(part);
+    }
+    let mut half = [0u8; 32];
+    half.copy_from_slice(&h.finalize()[..32]);
+    half
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ripple_time_to_unix, sha512_half, transaction_leaf, unix_time_to_ripple,
+        verify_transaction_hash,
+    };
+    use crate::types::ledger::{BinaryTransaction, Ledger};
+    use serde_xrpl::hash_prefixes;
+
+    #[test]
+    fn ripple_epoch_conversion() {
+        assert_eq!(ripple_time_to_unix(0), 946684800);
+        // 2013-01-01 03:21:10 UTC.
+        assert_eq!(ripple_time_to_unix(410325670), 1357010470);
+        assert_eq!(unix_time_to_ripple(1357010470), Some(410325670));
+        assert_eq!(unix_time_to_ripple(0), None);
+    }
+
+    #[test]
+    fn ledger_close_interval() {
+        let previous = Ledger {
+            close_time: Some(410325670),
+            ..Default::default()
+        };
+        let ledger = Ledger {
+            close_time: Some(410325680),
+            ..Default::default()
+        };
+        assert_eq!(ledger.close_time_unix(), Some(1357010480));
+        assert_eq!(ledger.close_interval(&previous), Some(10));
+        assert_eq!(ledger.close_interval(&Ledger::default()), None);
+    }
+
+    #[test]
+    fn verify_empty_transaction_tree() {
+        let ledger = Ledger {
+            transaction_hash: Some("0".repeat(64)),
+            ..Default::default()
+        };
+        assert!(verify_transaction_hash(&ledger, &[]));
+        assert!(!verify_transaction_hash(
+            &ledger,
+            &[BinaryTransaction {
+                tx_blob: "1200002200000000".to_owned(),
+                meta: "201C00000000".to_owned(),
+            }]
+        ));
+    }
+
+    /// Hashes an inner node from its non-empty branches.
+    fn inner_node(branches: &[(usize, [u8; 32])]) -> [u8; 32] {
+        let mut children = [[0u8; 32]; 16];
+        for (branch, hash) in branches {
+            children[*branch] = *hash;
+        }
+        let mut parts: Vec<&[u8]> = vec![&hash_prefixes::INNER_NODE];
+        parts.extend(children.iter().map(|c| &c[..]));
+        sha512_half(&parts)
+    }
+
+    #[test]
+    fn verify_transaction_tree_with_shared_nibble() {
+        // Signed payments from the genesis account with sequences 1 to 3, whose ids start with
+        // 83, A3 and 84, so the first and last share the branch under the root.
+        let transactions = [
+            BinaryTransaction {
+                tx_blob: "1200002400000001201B0000001461400000003B9ACA0068400000000000000A73210330E7FC9D56BB25D6893BA3F317AE5BCF33B3291BD63DB32654A313222F7FD0207447304502210098A3AC68213F71CFD2948E667A782D3776CC36C6A865DD306A68E55B5DAB4A3302206CCDBAA746B1F655FD9F06ABD1DB574D3C02D70DA81EB6458A90929C6D8E045B8114B5F762798A53D543A014CAF8B297CFF8F2F937E883141014D5F5DFAC076C25809A8C828DDE1EEC60CA7E".to_owned(),
+                meta: "201C00000000031000".to_owned(),
+            },
+            BinaryTransaction {
+                tx_blob: "1200002400000002201B0000001461400000003B9ACA0068400000000000000A73210330E7FC9D56BB25D6893BA3F317AE5BCF33B3291BD63DB32654A313222F7FD02074463044022048EB9F3EFBCDADFD2DA5E0CFE20F4C489A3BC0A2D405651E7067262BDA3D778502203B04E522A7B75B1F3B3A28400378A7E40D2C239E713AC72D800A7AFC32D1E8F38114B5F762798A53D543A014CAF8B297CFF8F2F937E883141014D5F5DFAC076C25809A8C828DDE1EEC60CA7E".to_owned(),
+                meta: "201C00000001031000".to_owned(),
+            },
+            BinaryTransaction {
+                tx_blob: "1200002400000003201B0000001461400000003B9ACA0068400000000000000A73210330E7FC9D56BB25D6893BA3F317AE5BCF33B3291BD63DB32654A313222F7FD0207446304402205FAFD66CB8A8810BBFD3326D6B3F9BD9D04C32015DCC92A57B2AFC0B8EB81BA0022043F7778B247E07E1EBFFB8A5AFF263B5A6F39C74DA60C354E5639918261CF6AD8114B5F762798A53D543A014CAF8B297CFF8F2F937E883141014D5F5DFAC076C25809A8C828DDE1EEC60CA7E".to_owned(),
+                meta: "201C00000002031000".to_owned(),
+            },
+        ];
+        let leaves: Vec<([u8; 32], [u8; 32])> = transactions
+            .iter()
+            .map(|tx| {
+                transaction_leaf(
+                    &hex::decode(&tx.tx_blob).unwrap(),
+                    &hex::decode(&tx.meta).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            hex::encode_upper(leaves[0].0),
+            "83BFD6AF8E34CA0B0F56270887BDD2EF0106711FEB9BADBC69C9D366DD845A84"
+        );
+        assert_eq!(
+            hex::encode_upper(leaves[1].0),
+            "A31DF2F46ED3E16107EE98F71902CB163DE401CBB0E0AC97353BC6E0C4E95F1A"
+        );
+        assert_eq!(
+            hex::encode_upper(leaves[2].0),
+            "848A168972E059BCB34E94247F00C928BB43B51FB0B8F6DC3966D21183C0BC8B"
+        );
+        // The leaves sharing the first nibble hang from an inner node split on the second one.
+        let shared = inner_node(&[(0x3, leaves[0].1), (0x4, leaves[2].1)]);
+        let root = inner_node(&[(0x8, shared), (0xA, leaves[1].1)]);
+        let ledger = Ledger {
+            transaction_hash: Some(hex::encode_upper(root)),
+            ..Default::default()
+        };
+        assert!(verify_transaction_hash(&ledger, &transactions));
+        let mut reordered = transactions.clone();
+        reordered.reverse();
+        assert!(verify_transaction_hash(&ledger, &reordered));
+        assert!(!verify_transaction_hash(&ledger, &transactions[..2]));
+        let mut altered = transactions.clone();
+        altered[2].meta = "201C00000002031001".to_owned();
+        assert!(!verify_transaction_hash(&ledger, &altered));
+    }
+}
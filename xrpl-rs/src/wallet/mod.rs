@@ -1,21 +1,28 @@
-use std::convert::TryInto;
-
+use ed25519_dalek::{
+    Keypair as Ed25519KeyPair, PublicKey as Ed25519PublicKey, SecretKey as Ed25519SecretKey,
+    SignatureError as Ed25519Error, Signer as _,
+};
 use hex_literal::hex;
+use rand::{rngs::OsRng, CryptoRng, RngCore};
 use rust_decimal::Decimal;
 use secp256k1::{
-    rand::rngs::OsRng, All, Error as Secp256k1Error, KeyPair as Secp256k1KeyPair, Message,
+    All, Error as Secp256k1Error, KeyPair as Secp256k1KeyPair, Message,
     PublicKey as Secp256k1PublicKey, Secp256k1, SecretKey as Secp256k1SecretKey,
 };
 use serde::Serialize;
 use serde_json::json;
 use serde_xrpl::types::Hash256;
 
-use crate::transaction::types::{PaymentChannelClaim, Transaction};
+use crate::transaction::decode_account_id;
+use crate::transaction::transaction_hash;
+use crate::transaction::types::{
+    Payment, PaymentChannelClaim, Signer, Transaction, TransactionType,
+};
 use crate::types::account::AccountInfoRequest;
 use crate::types::fee::FeeRequest;
 use crate::types::ledger::LedgerRequest;
-use crate::types::{BigInt, CurrencyAmount};
-use crate::{Error as XRPLError, Transport, XRPL};
+use crate::types::{ApiErrorCode, BigInt, ClassicAddress, CurrencyAmount};
+use crate::{ApiError, Error as XRPLError, Transport, ValidationError, XRPL};
 use lazy_static::lazy_static;
 use ripemd::{Digest, Ripemd160};
 use sha2::{Sha256, Sha512};
@@ -23,9 +30,27 @@ use sha2::{Sha256, Sha512};
 lazy_static! {
     static ref DEFAULT_MAX_FEE: BigInt = BigInt(100);
     static ref DEFAULT_LEDGER_OFFSET: u32 = 20; // Approx 1 minute.
+    /// A context shared by all secp256k1 operations, as creating one is expensive.
+    static ref SECP256K1: Secp256k1<All> = Secp256k1::new();
 }
 
 static FAMILY_SEED: u8 = 0x21;
+static ED25519_SEED: [u8; 3] = [0x01, 0xE1, 0x4B];
+static ED25519_PREFIX: u8 = 0xED;
+
+/// The BIP44 coin type registered for XRP.
+#[cfg(feature = "mnemonic")]
+const XRP_COIN_TYPE: u32 = 144;
+#[cfg(feature = "mnemonic")]
+const HARDENED_KEY_OFFSET: u32 = 0x80000000;
+
+/// The serialized size, in bytes, of a reference transaction for fee purposes. Larger
+/// transactions, such as those with large memos or NFT URIs, may be charged a higher fee.
+pub const REFERENCE_TRANSACTION_SIZE: usize = 1024;
+
+/// The percentage by which a transaction's fee must exceed the fee of the queued transaction it
+/// replaces.
+pub const REPLACEMENT_FEE_INCREASE_PERCENT: u64 = 25;
 
 #[derive(Debug)]
 pub enum Error {
@@ -35,8 +60,19 @@ pub enum Error {
     FeeRequired,
     FeeAboveMax,
     InvalidDrops,
+    InvalidSeed,
     Secp256k1Error(Secp256k1Error),
+    Ed25519Error(Ed25519Error),
+    #[cfg(feature = "mnemonic")]
+    InvalidMnemonic(bip39::Error),
     LastLedgerSequenceRequired,
+    TransactionTooLarge(usize),
+    ZeroAmount,
+    ClawbackFromSelf,
+    ReplacementFeeTooLow(BigInt),
+    /// The account doesn't exist in the ledger, as it has never been funded with enough XRP to
+    /// meet the reserve.
+    AccountNotFound,
 }
 
 impl From<XRPLError> for Error {
@@ -45,12 +81,25 @@ impl From<XRPLError> for Error {
     }
 }
 
-pub enum Signer {
-    Secp256k1(Secp256k1<All>),
+/// A signed transaction, ready to be submitted.
+#[derive(Debug, Clone)]
+pub struct SignedTransaction {
+    /// The signed transaction, including its signature and hash.
+    pub transaction: Transaction,
+    /// The hex encoded serialized transaction.
+    pub tx_blob: String,
 }
 
 pub enum KeyPair {
     Secp256k1(Secp256k1KeyPair),
+    Ed25519(Ed25519KeyPair),
+}
+
+/// The signing algorithm of a key pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    Secp256k1,
+    Ed25519,
 }
 
 pub struct Wallet {
@@ -59,36 +108,93 @@ pub struct Wallet {
     fee: Option<BigInt>,
     max_fee: BigInt,
     ledger_offset: u32,
-    secret: String,
+    // The family seed the keys were derived from, if any.
+    secret: Option<String>,
 }
 
 impl Wallet {
     pub fn new_random() -> Result<Self, Error> {
-        let secret = generate_random_secret()?;
+        Self::new_random_from_rng(&mut OsRng)
+    }
+    /// Creates a wallet from a random seed drawn from the provided RNG. Passing a seeded RNG
+    /// makes the generated wallet reproducible, which is useful in tests.
+    pub fn new_random_from_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Result<Self, Error> {
+        let secret = generate_random_secret(rng)?;
         Self::from_secret(&secret)
     }
     pub fn address(&self) -> String {
-        let sha = sha256(match &self.keypair {
-            KeyPair::Secp256k1(keypair) => {
-                hex::decode(&Secp256k1PublicKey::from_keypair(&keypair).to_string()).unwrap()
+        address_from_public_key(&self.public_key_bytes())
+    }
+    // Returns the 33 byte public key as it appears in SigningPubKey, either a compressed
+    // secp256k1 key or an ed25519 key prefixed with 0xED.
+    fn public_key_bytes(&self) -> Vec<u8> {
+        match &self.keypair {
+            KeyPair::Secp256k1(keypair) => Secp256k1PublicKey::from_keypair(keypair)
+                .serialize()
+                .to_vec(),
+            KeyPair::Ed25519(keypair) => {
+                [&[ED25519_PREFIX][..], keypair.public.as_bytes()].concat()
             }
-        });
-        let rip = ripemd160(&sha);
-        let prefixed = [vec![0x00], rip].concat();
-        let chk = double_sha256(&prefixed)[0..4].to_vec();
-        bs58::encode([prefixed, chk].concat())
-            .with_alphabet(bs58::Alphabet::RIPPLE)
-            .into_string()
+        }
     }
+    /// Creates a wallet from a seed, using the key type the seed is encoded for: seeds starting
+    /// with `sEd` derive Ed25519 keys, other seeds derive secp256k1 keys.
     pub fn from_secret(secret: &str) -> Result<Self, Error> {
-        let keypair = keypair_from_secret(secret)?;
+        let (key_type, _) = decode_seed(secret)?;
+        Self::from_secret_with_type(secret, key_type)
+    }
+    /// Creates a wallet from a seed, deriving keys of the provided type regardless of the key
+    /// type the seed is encoded for, like `wallet_propose` with a `key_type`.
+    pub fn from_secret_with_type(secret: &str, key_type: KeyType) -> Result<Self, Error> {
+        let (_, entropy) = decode_seed(secret)?;
+        let keypair = match key_type {
+            KeyType::Secp256k1 => secp256k1_keypair_from_entropy(&entropy)?,
+            KeyType::Ed25519 => ed25519_keypair_from_entropy(&entropy)?,
+        };
         Ok(Self {
             keypair,
             sequence: None,
             fee: None,
             max_fee: DEFAULT_MAX_FEE.to_owned(),
             ledger_offset: DEFAULT_LEDGER_OFFSET.to_owned(),
-            secret: secret.to_owned(),
+            secret: Some(secret.to_owned()),
+        })
+    }
+    /// Creates a wallet from a passphrase, deriving the seed the way `wallet_propose` does when
+    /// given a passphrase: the seed entropy is the first 16 bytes of the SHA-512Half of the
+    /// passphrase. Passphrase derived keys are easily guessed, use only to recover legacy accounts.
+    pub fn from_passphrase(passphrase: &str) -> Result<Self, Error> {
+        let mut h = Sha512::new();
+        h.update(passphrase.as_bytes());
+        Self::from_secret(&encode_secret(&h.finalize()[..16]))
+    }
+    /// Creates a wallet from a BIP39 mnemonic, deriving a secp256k1 key on the BIP44 path
+    /// `m/44'/144'/{account_index}'/0/0` used by most XRP hardware and exchange wallets.
+    #[cfg(feature = "mnemonic")]
+    pub fn from_mnemonic(phrase: &str, account_index: u32) -> Result<Self, Error> {
+        let seed = bip39::Mnemonic::parse(phrase)
+            .map_err(|e| Error::InvalidMnemonic(e))?
+            .to_seed("");
+        let path = [
+            HARDENED_KEY_OFFSET + 44,
+            HARDENED_KEY_OFFSET + XRP_COIN_TYPE,
+            HARDENED_KEY_OFFSET + account_index,
+            0,
+            0,
+        ];
+        let (mut secret_key, mut chain_code) = bip32_master_key(&seed)?;
+        for index in path {
+            let (child_key, child_chain_code) = bip32_child_key(&secret_key, &chain_code, index)?;
+            secret_key = child_key;
+            chain_code = child_chain_code;
+        }
+        Ok(Self {
+            keypair: KeyPair::Secp256k1(Secp256k1KeyPair::from_secret_key(&SECP256K1, secret_key)),
+            sequence: None,
+            fee: None,
+            max_fee: DEFAULT_MAX_FEE.to_owned(),
+            ledger_offset: DEFAULT_LEDGER_OFFSET.to_owned(),
+            secret: None,
         })
     }
     pub fn set_sequence(&mut self, sequence: u32) {
@@ -100,12 +206,10 @@ impl Wallet {
     pub fn set_max_fee(&mut self, drops: BigInt) {
         self.max_fee = drops;
     }
-    pub fn set_ledger_offset<T: TryInto<BigInt>>(
-        &mut self,
-        ledger_offset: u32,
-    ) -> Result<(), Error> {
+    /// Sets the number of ledgers after the current validated ledger that filled transactions
+    /// remain valid for.
+    pub fn set_ledger_offset(&mut self, ledger_offset: u32) {
         self.ledger_offset = ledger_offset;
-        Ok(())
     }
     pub async fn fill_and_sign<T: Transport>(
         &mut self,
@@ -115,10 +219,30 @@ impl Wallet {
         self.auto_fill_fields(tx, xrpl).await?;
         self.sign(tx)
     }
+    /// Fills and signs the transaction like `fill_and_sign`, but with a LastLedgerSequence offset
+    /// that overrides the wallet's ledger offset for this transaction only.
+    pub async fn fill_and_sign_with_offset<T: Transport>(
+        &mut self,
+        tx: &mut Transaction,
+        xrpl: &XRPL<T>,
+        ledger_offset: u32,
+    ) -> Result<String, Error> {
+        self.auto_fill_fields_with_offset(tx, xrpl, ledger_offset).await?;
+        self.sign(tx)
+    }
     pub async fn auto_fill_fields<T: Transport>(
         &mut self,
         tx: &mut Transaction,
         xrpl: &XRPL<T>,
+    ) -> Result<(), Error> {
+        let ledger_offset = self.ledger_offset;
+        self.auto_fill_fields_with_offset(tx, xrpl, ledger_offset).await
+    }
+    pub async fn auto_fill_fields_with_offset<T: Transport>(
+        &mut self,
+        tx: &mut Transaction,
+        xrpl: &XRPL<T>,
+        ledger_offset: u32,
     ) -> Result<(), Error> {
         if tx.flags.is_none() {
             // tfFullyCanonicalSig is flags is not otherwise specified.
@@ -128,9 +252,15 @@ impl Wallet {
         tx.account = self.address();
         // If there is no sequence specified, then fetch from the ledger.
         if self.sequence.is_none() {
-            let mut req = AccountInfoRequest::default();
-            req.account = self.address();
-            let account_info = xrpl.account_info(req).await?;
+            let req = AccountInfoRequest::new(ClassicAddress::new(&self.address())?);
+            let account_info = match xrpl.account_info(req).await {
+                Err(XRPLError::Api(ApiError::Response(e)))
+                    if e.error == Some(ApiErrorCode::ActNotFound) =>
+                {
+                    return Err(Error::AccountNotFound);
+                }
+                res => res?,
+            };
             self.sequence = Some(account_info.account_data.sequence);
         }
         // Set the sequence and increment.
@@ -163,118 +293,254 @@ impl Wallet {
             .ledger_index
             .ok_or(Error::LastLedgerSequenceRequired)?
             .0
-            + self.ledger_offset;
+            + ledger_offset;
         Ok(())
     }
     // Signs the provided transaction updating the corresponding transaction fields and returns
     // the hex encoded serialized transaction.
     pub fn sign(&self, tx: &mut Transaction) -> Result<String, Error> {
-        match &self.keypair {
-            KeyPair::Secp256k1(keypair) => {
-                let secp = Secp256k1::new();
-                tx.signing_pub_key = Secp256k1PublicKey::from_keypair(keypair).to_string();
-                let tx_blob_for_signing =
-                    serde_xrpl::ser::to_bytes_for_signing(&serde_json::to_value(&tx).unwrap())
-                        .unwrap();
-                let mut mh = Sha512::new();
-                mh.update(&tx_blob_for_signing);
-                let mhh = mh.finalize()[..32].to_vec();
-                let message = Message::from_slice(&mhh).unwrap();
-                let sig = secp.sign_ecdsa(&message, &Secp256k1SecretKey::from_keypair(keypair));
-                tx.txn_signature = Some(sig.to_string().to_uppercase());
-            }
+        Ok(hex::encode(self.sign_to_bytes(tx)?).to_uppercase())
+    }
+    // Signs the provided transaction updating the corresponding transaction fields and returns
+    // the serialized transaction bytes.
+    pub fn sign_to_bytes(&self, tx: &mut Transaction) -> Result<Vec<u8>, Error> {
+        check_amount(tx)?;
+        tx.check_extra().map_err(XRPLError::from)?;
+        tx.signing_pub_key = self.public_key();
+        let tx_blob_for_signing =
+            serde_xrpl::ser::to_bytes_for_signing(&to_json_value(&tx)?).map_err(XRPLError::from)?;
+        tx.txn_signature = Some(self.sign_bytes(&tx_blob_for_signing));
+        let tx_blob = serde_xrpl::ser::to_bytes(&to_json_value(&tx)?).map_err(XRPLError::from)?;
+        self.check_transaction_size(tx, &tx_blob)?;
+        tx.hash = Some(transaction_hash(&tx_blob));
+        Ok(tx_blob)
+    }
+    /// Signs the provided transaction as one of the signers of a multisigning account, clearing
+    /// its SigningPubKey as multisigned transactions require. The signatures of all signers are
+    /// combined into the submitted transaction with `combine_signers`.
+    ///
+    /// The Fee is signed, so the transaction must be prepared with `prepare_multisign` before any
+    /// signer signs, setting the Fee to the cost of the multisigned transaction.
+    pub fn multi_sign(&self, tx: &mut Transaction) -> Result<Signer, Error> {
+        check_amount(tx)?;
+        tx.check_extra().map_err(XRPLError::from)?;
+        tx.signing_pub_key = String::new();
+        tx.txn_signature = None;
+        let account = self.address();
+        let account_id = decode_account_id(&account).map_err(XRPLError::from)?;
+        let tx_blob_for_signing =
+            serde_xrpl::ser::to_bytes_for_multi_signing(&to_json_value(&tx)?, &account_id)
+                .map_err(XRPLError::from)?;
+        Ok(Signer {
+            account,
+            signing_pub_key: self.public_key(),
+            txn_signature: self.sign_bytes(&tx_blob_for_signing),
+        })
+    }
+    /// Signs a copy of a transaction that is stuck in the queue with the same sequence and a higher
+    /// fee, so that submitting it replaces the stuck transaction. The new fee must be at least
+    /// `REPLACEMENT_FEE_INCREASE_PERCENT` higher than the stuck transaction's fee; the minimum fee
+    /// is returned in `Error::ReplacementFeeTooLow` otherwise.
+    pub fn replace_transaction(
+        &self,
+        stuck_tx: &Transaction,
+        new_fee: BigInt,
+    ) -> Result<SignedTransaction, Error> {
+        let min_fee =
+            BigInt((stuck_tx.fee.0 * (100 + REPLACEMENT_FEE_INCREASE_PERCENT) + 99) / 100);
+        if new_fee < min_fee {
+            return Err(Error::ReplacementFeeTooLow(min_fee));
+        }
+        if new_fee > self.max_fee {
+            return Err(Error::FeeAboveMax);
         }
-        let tx_blob = serde_xrpl::ser::to_bytes(&serde_json::to_value(&tx).unwrap()).unwrap();
-        let mut th = Sha512::new();
-        th.update(&[hex!("54584e00").to_vec(), tx_blob.to_vec()].concat());
-        let transaction_hash = th.finalize()[..32].to_vec();
-        tx.hash = Some(hex::encode(transaction_hash).to_uppercase());
-        Ok(hex::encode(tx_blob).to_uppercase())
+        let mut transaction = stuck_tx.clone();
+        transaction.fee = new_fee;
+        transaction.txn_signature = None;
+        transaction.hash = None;
+        let tx_blob = self.sign(&mut transaction)?;
+        Ok(SignedTransaction {
+            transaction,
+            tx_blob,
+        })
+    }
+    // Checks that a transaction larger than the reference size would not incur a fee above the
+    // max fee once the fee is scaled by its size.
+    fn check_transaction_size(&self, tx: &Transaction, tx_blob: &[u8]) -> Result<(), Error> {
+        if tx_blob.len() <= REFERENCE_TRANSACTION_SIZE {
+            return Ok(());
+        }
+        let multiplier =
+            (tx_blob.len() + REFERENCE_TRANSACTION_SIZE - 1) / REFERENCE_TRANSACTION_SIZE;
+        let scaled_fee = tx.fee.0.saturating_mul(multiplier as u64);
+        if scaled_fee > self.max_fee.0 {
+            return Err(Error::TransactionTooLarge(tx_blob.len()));
+        }
+        log::warn!(
+            "transaction is {} bytes, above the {} byte reference size, and may require a fee of up to {} drops",
+            tx_blob.len(),
+            REFERENCE_TRANSACTION_SIZE,
+            scaled_fee
+        );
+        Ok(())
     }
+    /// Returns the public key as hex, as it appears in SigningPubKey. Ed25519 keys are prefixed
+    /// with ED.
     pub fn public_key(&self) -> String {
         match &self.keypair {
             KeyPair::Secp256k1(keypair) => {
                 return Secp256k1PublicKey::from_keypair(keypair).to_string();
             }
+            KeyPair::Ed25519(_) => hex::encode_upper(self.public_key_bytes()),
         }
     }
     pub fn private_key(&self) -> String {
         match &self.keypair {
             KeyPair::Secp256k1(keypair) => return keypair.display_secret().to_string(),
+            KeyPair::Ed25519(keypair) => {
+                format!("ED{}", hex::encode_upper(keypair.secret.as_bytes()))
+            }
         }
     }
-    pub fn sign_message<T: Serialize>(&self, message: T) -> Result<String, Error> {
+    /// Returns the signing algorithm of this wallet's keys.
+    pub fn key_type(&self) -> KeyType {
         match &self.keypair {
-            KeyPair::Secp256k1(keypair) => {
-                let secp = Secp256k1::new();
-                let message_blob_for_signing =
-                    serde_xrpl::ser::to_bytes_for_claim(&serde_json::to_value(&message).unwrap())
-                        .unwrap();
-                let mut mh = Sha512::new();
-                mh.update(&message_blob_for_signing);
-                let mhh = mh.finalize()[..32].to_vec();
-                let message = Message::from_slice(&mhh).unwrap();
-                let sig = secp.sign_ecdsa(&message, &Secp256k1SecretKey::from_keypair(keypair));
-                Ok(sig.to_string().to_uppercase())
-            }
+            KeyPair::Secp256k1(_) => KeyType::Secp256k1,
+            KeyPair::Ed25519(_) => KeyType::Ed25519,
         }
     }
+    pub fn sign_message<T: Serialize>(&self, message: T) -> Result<String, Error> {
+        let message_blob_for_signing =
+            serde_xrpl::ser::to_bytes_for_claim(&to_json_value(&message)?)
+                .map_err(XRPLError::from)?;
+        Ok(self.sign_bytes(&message_blob_for_signing))
+    }
     pub fn sign_payment_channel_claim(
         &self,
         channel: String,
         amount: BigInt,
     ) -> Result<String, Error> {
+        let mut message = hex!("434c4d00").to_vec();
+        message.extend(Hash256(channel).to_bytes());
+        message.extend(amount.0.to_be_bytes());
+        Ok(self.sign_bytes(&message))
+    }
+    // Signs the message, returning the signature as hex. Secp256k1 keys sign the SHA-512Half of
+    // the message, while Ed25519 keys sign the message itself, as Ed25519 hashes it internally.
+    fn sign_bytes(&self, message: &[u8]) -> String {
         match &self.keypair {
             KeyPair::Secp256k1(keypair) => {
-                let secp = Secp256k1::new();
                 let mut mh = Sha512::new();
-                let prefix = hex!("434c4d00").to_vec();
-                let channel_bytes = Hash256(channel).to_bytes();
-                let amount_bytes = amount.0.to_be_bytes().to_vec();
-                mh.update([prefix, channel_bytes, amount_bytes].concat());
+                mh.update(message);
                 let mhh = mh.finalize()[..32].to_vec();
                 let message = Message::from_slice(&mhh).unwrap();
-                let sig = secp.sign_ecdsa(&message, &Secp256k1SecretKey::from_keypair(keypair));
-                Ok(sig.to_string().to_uppercase())
+                let sig =
+                    SECP256K1.sign_ecdsa(&message, &Secp256k1SecretKey::from_keypair(keypair));
+                sig.to_string().to_uppercase()
             }
+            KeyPair::Ed25519(keypair) => hex::encode_upper(keypair.sign(message).to_bytes()),
+        }
+    }
+}
+
+// Rejects transactions that the network would reject as malformed: payments that would deliver
+// nothing, XRP amounts above the total supply and payments with inconsistent amounts.
+fn check_amount(tx: &Transaction) -> Result<(), Error> {
+    if !tx.fee.is_valid_drops() {
+        return Err(Error::InvalidDrops);
+    }
+    match &tx.tx {
+        Some(TransactionType::Payment(Payment {
+            amount: CurrencyAmount::XRP(drops),
+            ..
+        })) if !drops.is_valid_drops() => Err(Error::InvalidDrops),
+        Some(TransactionType::Payment(payment)) if payment.amount.is_zero() => {
+            Err(Error::ZeroAmount)
         }
+        Some(TransactionType::Payment(payment)) => Ok(payment.validate(tx.flags)?),
+        Some(TransactionType::Clawback(clawback)) => match (&clawback.amount, &clawback.holder) {
+            // The issuer subfield of a trust line amount names the holder being clawed back from.
+            (CurrencyAmount::IssuedCurrency(amount), None) if amount.issuer == tx.account => {
+                Err(Error::ClawbackFromSelf)
+            }
+            (CurrencyAmount::MPT(_), Some(holder)) if *holder == tx.account => {
+                Err(Error::ClawbackFromSelf)
+            }
+            (CurrencyAmount::IssuedCurrency(_), None) | (CurrencyAmount::MPT(_), Some(_)) => Ok(()),
+            _ => Err(XRPLError::from(ValidationError::InvalidAmount(
+                "Clawback takes a trust line amount, or an MPT amount and a holder".to_owned(),
+            ))
+            .into()),
+        },
+        _ => Ok(()),
     }
 }
 
-fn decode_secret(secret: &str) -> Result<Vec<u8>, Error> {
-    Ok(bs58::decode(secret.as_bytes())
+/// Derives the classic address of an account from its 33 byte public key. Ed25519 public keys
+/// must include their 0xED prefix.
+pub fn address_from_public_key(public_key: &[u8]) -> String {
+    let rip = ripemd160(&sha256(public_key));
+    let prefixed = [vec![0x00], rip].concat();
+    let chk = double_sha256(&prefixed)[0..4].to_vec();
+    bs58::encode([prefixed, chk].concat())
+        .with_alphabet(bs58::Alphabet::RIPPLE)
+        .into_string()
+}
+
+// Decodes a seed into the key type it is encoded for and its 16 bytes of entropy.
+fn decode_seed(secret: &str) -> Result<(KeyType, Vec<u8>), Error> {
+    let decoded = bs58::decode(secret.as_bytes())
         .with_alphabet(bs58::alphabet::Alphabet::RIPPLE)
         .with_check(None)
         .into_vec()
-        .map_err(|e| Error::InvalidSecret(e))?[1..]
-        .to_vec())
+        .map_err(|e| Error::InvalidSecret(e))?;
+    match decoded.len() {
+        17 if decoded[0] == FAMILY_SEED => Ok((KeyType::Secp256k1, decoded[1..].to_vec())),
+        19 if decoded[..3] == ED25519_SEED => Ok((KeyType::Ed25519, decoded[3..].to_vec())),
+        _ => Err(Error::InvalidSeed),
+    }
+}
+
+/// Converts a value to JSON for the binary serializer, reporting failures as serialization errors.
+fn to_json_value<T: Serialize>(value: &T) -> Result<serde_json::Value, Error> {
+    serde_json::to_value(value)
+        .map_err(|e| XRPLError::from(serde_xrpl::error::Error::Message(e.to_string())).into())
+}
+
+fn generate_random_secret<R: RngCore + CryptoRng>(rng: &mut R) -> Result<String, Error> {
+    let mut r = [0u8; 16];
+    rng.fill_bytes(&mut r);
+    Ok(encode_secret(&r))
 }
 
-fn generate_random_secret() -> Result<String, Error> {
-    let r: [u8; 16] = rand::random();
-    Ok(bs58::encode([vec![FAMILY_SEED], r.to_vec()].concat())
+fn encode_secret(entropy: &[u8]) -> String {
+    bs58::encode([vec![FAMILY_SEED], entropy.to_vec()].concat())
         .with_alphabet(bs58::alphabet::Alphabet::RIPPLE)
         .with_check()
-        .into_string())
+        .into_string()
 }
 
-fn keypair_from_secret(secret: &str) -> Result<KeyPair, Error> {
-    let decoded_secret = bs58::decode(secret.as_bytes())
-        .with_alphabet(bs58::alphabet::Alphabet::RIPPLE)
-        .with_check(None)
-        .into_vec()
-        .unwrap()[1..]
-        .to_vec();
-    let secp = Secp256k1::new();
+// Derives the Ed25519 key pair for seed entropy. The secret key is the SHA-512Half of the
+// entropy.
+fn ed25519_keypair_from_entropy(entropy: &[u8]) -> Result<KeyPair, Error> {
     let mut sh = Sha512::new();
-    sh.update([decoded_secret.to_vec(), 0u32.to_be_bytes().to_vec()].concat());
+    sh.update(entropy);
+    let secret =
+        Ed25519SecretKey::from_bytes(&sh.finalize()[..32]).map_err(|e| Error::Ed25519Error(e))?;
+    let public = Ed25519PublicKey::from(&secret);
+    Ok(KeyPair::Ed25519(Ed25519KeyPair { secret, public }))
+}
+
+fn secp256k1_keypair_from_entropy(entropy: &[u8]) -> Result<KeyPair, Error> {
+    let mut sh = Sha512::new();
+    sh.update([entropy.to_vec(), 0u32.to_be_bytes().to_vec()].concat());
     let secret = sh.finalize();
     let root_secret_key =
         Secp256k1SecretKey::from_slice(&secret[..32]).map_err(|e| Error::Secp256k1Error(e))?;
     let mut intermediate_hash = Sha512::new();
     intermediate_hash.update(
         [
-            Secp256k1PublicKey::from_secret_key(&secp, &root_secret_key)
+            Secp256k1PublicKey::from_secret_key(&SECP256K1, &root_secret_key)
                 .serialize()
                 .to_vec(),
             0u32.to_be_bytes().to_vec(),
@@ -288,10 +554,58 @@ fn keypair_from_secret(secret: &str) -> Result<KeyPair, Error> {
     account_secret_key
         .add_assign(&root_secret_key.serialize_secret())
         .map_err(|e| Error::Secp256k1Error(e))?;
-    let account_keypair = Secp256k1KeyPair::from_secret_key(&secp, account_secret_key);
+    let account_keypair = Secp256k1KeyPair::from_secret_key(&SECP256K1, account_secret_key);
     Ok(KeyPair::Secp256k1(account_keypair))
 }
 
+#[cfg(feature = "mnemonic")]
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    use secp256k1::hashes::{
+        hmac::{Hmac, HmacEngine},
+        sha512, Hash, HashEngine,
+    };
+    let mut engine = HmacEngine::<sha512::Hash>::new(key);
+    engine.input(data);
+    Hmac::<sha512::Hash>::from_engine(engine).into_inner()
+}
+
+#[cfg(feature = "mnemonic")]
+fn bip32_master_key(seed: &[u8]) -> Result<(Secp256k1SecretKey, Vec<u8>), Error> {
+    let i = hmac_sha512(b"Bitcoin seed", seed);
+    let secret_key =
+        Secp256k1SecretKey::from_slice(&i[..32]).map_err(|e| Error::Secp256k1Error(e))?;
+    Ok((secret_key, i[32..].to_vec()))
+}
+
+#[cfg(feature = "mnemonic")]
+fn bip32_child_key(
+    parent_key: &Secp256k1SecretKey,
+    chain_code: &[u8],
+    index: u32,
+) -> Result<(Secp256k1SecretKey, Vec<u8>), Error> {
+    let data = if index >= HARDENED_KEY_OFFSET {
+        [
+            &[0u8][..],
+            &parent_key.serialize_secret(),
+            &index.to_be_bytes(),
+        ]
+        .concat()
+    } else {
+        [
+            &Secp256k1PublicKey::from_secret_key(&SECP256K1, parent_key).serialize()[..],
+            &index.to_be_bytes(),
+        ]
+        .concat()
+    };
+    let i = hmac_sha512(chain_code, &data);
+    let mut child_key =
+        Secp256k1SecretKey::from_slice(&i[..32]).map_err(|e| Error::Secp256k1Error(e))?;
+    child_key
+        .add_assign(&parent_key.serialize_secret())
+        .map_err(|e| Error::Secp256k1Error(e))?;
+    Ok((child_key, i[32..].to_vec()))
+}
+
 fn sha256(i: impl AsRef<[u8]>) -> Vec<u8> {
     let mut h = Sha256::new();
     h.update(i);
@@ -307,3 +621,284 @@ fn ripemd160(i: impl AsRef<[u8]>) -> Vec<u8> {
     r.update(&i);
     r.finalize().to_vec()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{address_from_public_key, Error, KeyType, Wallet};
+    use crate::transaction::types::{Clawback, Payment};
+    use crate::transaction::{
+        combine_signers, decode_account_id, prepare_multisign, serialize_signed,
+    };
+    use crate::types::{BigInt, CurrencyAmount, IssuedCurrencyAmount, MPTAmount, MAX_XRP_DROPS};
+    use ed25519_dalek::Verifier;
+    use rand::{CryptoRng, RngCore};
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn from_passphrase() {
+        let wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        assert_eq!(
+            wallet.secret,
+            Some("snoPBrXtMeMyMHUVTgbuqAfg1SUTb".to_owned())
+        );
+        assert_eq!(wallet.address(), "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh");
+    }
+
+    // An RNG that always yields zero bytes, so generated wallets are known in advance.
+    struct ZeroRng;
+
+    impl RngCore for ZeroRng {
+        fn next_u32(&mut self) -> u32 {
+            0
+        }
+        fn next_u64(&mut self) -> u64 {
+            0
+        }
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(0);
+        }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for ZeroRng {}
+
+    #[test]
+    fn new_random_from_rng() {
+        let wallet = Wallet::new_random_from_rng(&mut ZeroRng).unwrap();
+        assert_eq!(
+            wallet.secret,
+            Some("sp6JS7f14BuwFY8Mw6bTtLKWauoUs".to_owned())
+        );
+        assert_eq!(wallet.address(), "rGCkuB7PBr5tNy68tPEABEtcdno4hE6Y7f");
+        assert_ne!(Wallet::new_random().unwrap().address(), wallet.address());
+    }
+
+    #[test]
+    fn zero_payment_rejected() {
+        let wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(0),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        assert!(matches!(wallet.sign(&mut tx), Err(Error::ZeroAmount)));
+    }
+
+    #[test]
+    fn drops_above_supply_rejected() {
+        let wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(MAX_XRP_DROPS + 1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        tx.account = wallet.address();
+        assert!(matches!(wallet.sign(&mut tx), Err(Error::InvalidDrops)));
+    }
+
+    #[test]
+    fn clawback_from_self_rejected() {
+        let wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        let mut tx = Clawback {
+            amount: CurrencyAmount::IssuedCurrency(IssuedCurrencyAmount {
+                value: Decimal::new(10, 0),
+                currency: "USD".to_owned(),
+                issuer: wallet.address(),
+            }),
+            holder: None,
+        }
+        .into_transaction();
+        tx.account = wallet.address();
+        assert!(matches!(wallet.sign(&mut tx), Err(Error::ClawbackFromSelf)));
+        let mpt = CurrencyAmount::MPT(MPTAmount {
+            value: "10".to_owned(),
+            mpt_issuance_id: "00000001A407AF5856CCF3C42619DAA925813FC955C72983".to_owned(),
+        });
+        let mut tx = Clawback {
+            amount: mpt.clone(),
+            holder: Some(wallet.address()),
+        }
+        .into_transaction();
+        tx.account = wallet.address();
+        assert!(matches!(wallet.sign(&mut tx), Err(Error::ClawbackFromSelf)));
+        // MPT clawbacks name the holder, and XRP can't be clawed back.
+        for (amount, holder) in [(mpt, None), (CurrencyAmount::xrp(10), None)] {
+            let mut tx = Clawback { amount, holder }.into_transaction();
+            tx.account = wallet.address();
+            assert!(matches!(
+                wallet.sign(&mut tx),
+                Err(Error::XRPLError(crate::Error::Validation(_)))
+            ));
+        }
+    }
+
+    #[test]
+    fn sign_without_account_fails() {
+        let wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        assert!(matches!(
+            wallet.sign_to_bytes(&mut tx),
+            Err(Error::XRPLError(crate::Error::Serialization(_)))
+        ));
+    }
+
+    #[test]
+    fn replace_transaction() {
+        let wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        tx.account = wallet.address();
+        tx.fee = BigInt(10);
+        tx.sequence = 7;
+        assert!(matches!(
+            wallet.replace_transaction(&tx, BigInt(12)),
+            Err(Error::ReplacementFeeTooLow(BigInt(13)))
+        ));
+        let signed = wallet.replace_transaction(&tx, BigInt(13)).unwrap();
+        assert_eq!(signed.transaction.fee, BigInt(13));
+        assert_eq!(signed.transaction.sequence, 7);
+        assert!(signed.transaction.txn_signature.is_some());
+    }
+
+    #[test]
+    fn ed25519_from_secret() {
+        let wallet = Wallet::from_secret("sEdSKaCy2JT7JaM7v95H9SxkhP9wS2r").unwrap();
+        assert_eq!(wallet.key_type(), KeyType::Ed25519);
+        assert_eq!(
+            wallet.public_key(),
+            "ED01FA53FA5A7E77798F882ECE20B1ABC00BB358A9E55A202D0D0676BD0CE37A63"
+        );
+        assert_eq!(
+            wallet.private_key(),
+            "EDB4C4E046826BD26190D09715FC31F4E6A728204EADD112905B08B14B7F15C4F3"
+        );
+        assert_eq!(wallet.address(), "rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD");
+        // The same entropy encoded as a secp256k1 seed.
+        let wallet =
+            Wallet::from_secret_with_type("sp5fghtJtpUorTwvof1NpDXAzNwf5", KeyType::Ed25519)
+                .unwrap();
+        assert_eq!(wallet.address(), "rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD");
+    }
+
+    #[test]
+    fn ed25519_sign() {
+        let wallet = Wallet::from_secret("sEdSKaCy2JT7JaM7v95H9SxkhP9wS2r").unwrap();
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        tx.account = wallet.address();
+        wallet.sign(&mut tx).unwrap();
+        assert_eq!(tx.signing_pub_key, wallet.public_key());
+        let signature = hex::decode(tx.txn_signature.take().unwrap()).unwrap();
+        tx.hash = None;
+        let signing_blob =
+            serde_xrpl::ser::to_bytes_for_signing(&serde_json::to_value(&tx).unwrap()).unwrap();
+        let public_key = hex::decode(wallet.public_key()).unwrap();
+        let public_key = ed25519_dalek::PublicKey::from_bytes(&public_key[1..]).unwrap();
+        let signature = ed25519_dalek::Signature::from_bytes(&signature).unwrap();
+        assert!(public_key.verify(&signing_blob, &signature).is_ok());
+    }
+
+    #[test]
+    fn secp256k1_sign_deterministic() {
+        let wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        tx.account = wallet.address();
+        let tx_blob = wallet.sign(&mut tx.clone()).unwrap();
+        assert_eq!(wallet.sign(&mut tx).unwrap(), tx_blob);
+        let channel = "00".repeat(32);
+        let first = wallet.sign_payment_channel_claim(channel.clone(), BigInt(1));
+        let second = wallet.sign_payment_channel_claim(channel, BigInt(1));
+        assert_eq!(first.unwrap(), second.unwrap());
+    }
+
+    #[test]
+    fn multi_sign() {
+        let secp256k1_wallet = Wallet::from_passphrase("masterpassphrase").unwrap();
+        let ed25519_wallet = Wallet::from_secret("sEdSKaCy2JT7JaM7v95H9SxkhP9wS2r").unwrap();
+        let mut tx = Payment {
+            amount: CurrencyAmount::xrp(1),
+            destination: "rp7pmm4rzTGmtZDuvrG1z9Xrm3KwHRipDw".to_owned(),
+            ..Default::default()
+        }
+        .into_transaction();
+        tx.account = "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys".to_owned();
+        prepare_multisign(&mut tx, BigInt(10), 2);
+        let ed25519_signer = ed25519_wallet.multi_sign(&mut tx).unwrap();
+        let secp256k1_signer = secp256k1_wallet.multi_sign(&mut tx).unwrap();
+        assert_eq!(tx.signing_pub_key, "");
+        assert_eq!(ed25519_signer.account, ed25519_wallet.address());
+
+        // Each signer signs the transaction with their own account ID appended.
+        let account_id = decode_account_id(&ed25519_wallet.address()).unwrap();
+        let signing_blob = serde_xrpl::ser::to_bytes_for_multi_signing(
+            &serde_json::to_value(&tx).unwrap(),
+            &account_id,
+        )
+        .unwrap();
+        let public_key = hex::decode(ed25519_wallet.public_key()).unwrap();
+        let public_key = ed25519_dalek::PublicKey::from_bytes(&public_key[1..]).unwrap();
+        let signature = hex::decode(&ed25519_signer.txn_signature).unwrap();
+        let signature = ed25519_dalek::Signature::from_bytes(&signature).unwrap();
+        assert!(public_key.verify(&signing_blob, &signature).is_ok());
+
+        combine_signers(&mut tx, vec![ed25519_signer, secp256k1_signer]).unwrap();
+        // rHb9... has account ID 0xB5F7... and rLUE... 0xD28B..., so rHb9... signs first.
+        let accounts: Vec<_> = tx
+            .signers
+            .iter()
+            .flatten()
+            .map(|signer| signer.account.clone())
+            .collect();
+        assert_eq!(
+            accounts,
+            [secp256k1_wallet.address(), ed25519_wallet.address()]
+        );
+        assert!(serialize_signed(&tx).is_ok());
+    }
+
+    #[test]
+    fn ed25519_address() {
+        let public_key =
+            hex::decode("ED01FA53FA5A7E77798F882ECE20B1ABC00BB358A9E55A202D0D0676BD0CE37A63")
+                .unwrap();
+        assert_eq!(
+            address_from_public_key(&public_key),
+            "rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD"
+        );
+    }
+
+    #[cfg(feature = "mnemonic")]
+    #[test]
+    fn from_mnemonic() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let wallet = Wallet::from_mnemonic(phrase, 0).unwrap();
+        assert_eq!(
+            wallet.public_key(),
+            "031d68bc1a142e6766b2bdfb006ccfe135ef2e0e2e94abb5cf5c9ab6104776fbae"
+        );
+        assert_eq!(wallet.address(), "rHsMGQEkVNJmpGWs8XUBoTBiAAbwxZN5v3");
+    }
+}